@@ -1,5 +1,18 @@
 pub mod mix_operations;
+pub mod mix_instruction_set;
+pub mod mix_word;
 pub mod mix_machine;
+pub mod mix_device;
+pub mod mix_trap;
+pub mod mix_tape;
+pub mod mix_disk;
+pub mod mix_char;
+pub mod mix_card;
+pub mod mix_printer;
+pub mod mix_terminal;
+pub mod mix_mock_device;
+pub mod mixal;
+mod mix_float;
 
 #[test]
 fn it_works() {
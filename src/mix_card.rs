@@ -0,0 +1,88 @@
+// The card reader (unit 16) and card punch (unit 17).
+//
+// A card deck is a host text file with one card of 80 columns per line. Each card is a
+// block of CARD_BLOCK_SIZE words, five characters to a word, in the MIX character code.
+// Short lines are padded with blanks. Reading past the last card is an error, as is
+// punching a character code with no character.
+
+use mix_char::{text_to_words, words_to_text};
+use mix_device::Device;
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+pub const CARD_BLOCK_SIZE: usize = 16;
+pub const CARD_COLUMNS: usize = 5 * CARD_BLOCK_SIZE;
+
+fn io_error(e: io::Error) -> MixMachineErr {
+    MixMachineErr{message: format!("Card I/O error: {}", e)}
+}
+
+pub struct CardReader {
+    deck: Box<dyn BufRead>,
+    cards_read: usize,
+}
+
+pub struct CardPunch {
+    output: Box<dyn Write>,
+}
+
+impl CardReader {
+    pub fn new(deck: Box<dyn BufRead>) -> CardReader {
+        CardReader{deck, cards_read: 0}
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<CardReader, MixMachineErr> {
+        let file = File::open(path).map_err(io_error)?;
+        Ok(CardReader::new(Box::new(BufReader::new(file))))
+    }
+
+    pub fn cards_read(&self) -> usize {
+        self.cards_read
+    }
+}
+
+impl CardPunch {
+    pub fn new(output: Box<dyn Write>) -> CardPunch {
+        CardPunch{output}
+    }
+
+    // Punched cards are added to the end of the file at path
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<CardPunch, MixMachineErr> {
+        let file = OpenOptions::new().create(true).append(true).open(path).map_err(io_error)?;
+        Ok(CardPunch::new(Box::new(file)))
+    }
+}
+
+impl<B: ByteSize> Device<B> for CardReader {
+    fn block_size(&self) -> usize {
+        CARD_BLOCK_SIZE
+    }
+
+    fn read(&mut self, block: &mut [B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        let mut line = String::new();
+        if self.deck.read_line(&mut line).map_err(io_error)? == 0 {
+            return Err(MixMachineErr{message: String::from("No more cards in the reader.")});
+        }
+        self.cards_read += 1;
+        let card = line.trim_end_matches(['\n', '\r']);
+        let words = text_to_words::<B>(card, CARD_BLOCK_SIZE)
+            .map_err(|e| MixMachineErr{message: format!("Card {}: {}", self.cards_read, e.message)})?;
+        block.copy_from_slice(&words);
+        Ok(())
+    }
+}
+
+impl<B: ByteSize> Device<B> for CardPunch {
+    fn block_size(&self) -> usize {
+        CARD_BLOCK_SIZE
+    }
+
+    fn write(&mut self, block: &[B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        let card = words_to_text::<B>(block)?;
+        writeln!(self.output, "{}", card).and_then(|_| self.output.flush()).map_err(io_error)
+    }
+}
@@ -0,0 +1,90 @@
+// The MIX character code of TAOCP 1.3.1, used by the character devices, NUM and CHAR.
+// Codes 0 to 55 are defined. Δ, Σ and Π are written with the Greek capitals, and code
+// 55 is the apostrophe, which may also be given as a typographic quote.
+//
+// A word holds five characters, one to a byte. Text is packed into words from the
+// left, and padded with blanks.
+
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+
+pub const UNPRINTABLE: char = '?';
+
+// The code of the digit 0. The digits 0 to 9 have consecutive codes.
+pub const DIGIT_ZERO: u8 = 30;
+
+const CHARACTERS: [char; 56] = [
+    ' ', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I',
+    'Δ', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R',
+    'Σ', 'Π', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9',
+    '.', ',', '(', ')', '+', '-', '*', '/', '=', '$',
+    '<', '>', '@', ';', ':', '\'',
+];
+
+pub fn code_to_char(code: u8) -> Option<char> {
+    CHARACTERS.get(code as usize).cloned()
+}
+
+// Lower case letters are read as upper case, since MIX has no lower case
+pub fn char_to_code(c: char) -> Option<u8> {
+    let c = match c {
+        '\u{2018}' | '\u{2019}' => '\'',
+        _ if c.is_ascii_lowercase() => c.to_ascii_uppercase(),
+        _ => c,
+    };
+    CHARACTERS.iter().position(|&d| d == c).map(|code| code as u8)
+}
+
+// Pack up to five characters into a positive word
+pub fn text_to_word<B: ByteSize>(text: &str) -> Result<B::Word, MixMachineErr> {
+    text_to_words::<B>(text, 1).map(|words| words[0])
+}
+
+// The five characters of a word. The sign is ignored.
+pub fn word_to_text<B: ByteSize>(word: B::Word) -> Result<String, MixMachineErr> {
+    words_to_text::<B>(&[word])
+}
+
+// Pack text into n words of five characters each, padding with spaces
+pub fn text_to_words<B: ByteSize>(text: &str, n: usize) -> Result<Vec<B::Word>, MixMachineErr> {
+    let mut codes = Vec::with_capacity(5 * n);
+    for c in text.chars() {
+        match char_to_code(c) {
+            Some(code) => codes.push(code as u64),
+            None => return Err(MixMachineErr{message: format!("Character {:?} is not in the MIX character set.", c)}),
+        }
+    }
+    if codes.len() > 5 * n {
+        return Err(MixMachineErr{message: format!("Text {:?} does not fit in {} words.", text, n)});
+    }
+    codes.resize(5 * n, 0u64);
+    Ok(codes.chunks(5).map(|bytes| B::make_word(true, [bytes[0], bytes[1], bytes[2], bytes[3], bytes[4]])).collect())
+}
+
+// The characters in the bytes of words. The signs are ignored.
+pub fn words_to_text<B: ByteSize>(words: &[B::Word]) -> Result<String, MixMachineErr> {
+    let mut text = String::with_capacity(5 * words.len());
+    for word in words {
+        for i in 1..6 {
+            let code = B::byte(B::from_word(*word), i);
+            match code_to_char(code as u8) {
+                Some(c) => text.push(c),
+                None => return Err(MixMachineErr{message: format!("Character code {} has no printable character.", code)}),
+            }
+        }
+    }
+    Ok(text)
+}
+
+// As words_to_text, but any byte that is not a MIX character appears as UNPRINTABLE and
+// trailing blanks are left off, for display on a printer or terminal
+pub fn words_to_printable<B: ByteSize>(words: &[B::Word]) -> String {
+    let mut text = String::with_capacity(5 * words.len());
+    for word in words {
+        for i in 1..6 {
+            text.push(code_to_char(B::byte(B::from_word(*word), i) as u8).unwrap_or(UNPRINTABLE));
+        }
+    }
+    String::from(text.trim_end_matches(' '))
+}
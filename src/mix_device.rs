@@ -0,0 +1,66 @@
+// Input-output devices, attached to the unit numbers used by IN, OUT, IOC, JRED and JBUS.
+// Any type implementing Device can be attached to a machine with attach_device(), so
+// new kinds of hardware can be simulated outside this crate.
+//
+// Each IN or OUT transfers one block of block_size() words between memory and the
+// device. The contents of rX are passed along, since disks and drums take the position
+// of the block from there.
+
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+
+// Units 0 to 20 are those described in TAOCP 1.3.1
+pub const MAX_UNIT: u8 = 20;
+
+pub trait Device<B: ByteSize> {
+    // Number of words in each block transferred by IN or OUT
+    fn block_size(&self) -> usize;
+
+    // IN: fill block with the next block of input
+    fn read(&mut self, _block: &mut [B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        Err(MixMachineErr{message: String::from("Device does not support input.")})
+    }
+
+    // OUT: write block to the device
+    fn write(&mut self, _block: &[B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        Err(MixMachineErr{message: String::from("Device does not support output.")})
+    }
+
+    // A busy unit has not yet finished its last operation. JBUS jumps while the unit is
+    // busy, JRED once it is ready, and IN, OUT and IOC wait until it is ready.
+    fn is_busy(&self) -> bool {
+        false
+    }
+
+    // Time in u for which the unit stays busy after each IN, OUT or IOC. This can be
+    // changed once the device is attached, with set_device_latency().
+    fn latency(&self) -> u32 {
+        0
+    }
+
+    // IOC: the meaning of the effective address m depends on the device
+    fn control(&mut self, _m: i16, _rx: B::Word) -> Result<(), MixMachineErr> {
+        Err(MixMachineErr{message: String::from("Device does not support control operations.")})
+    }
+}
+
+// Blocks kept in host files store each word as eight bytes, holding the u64 format
+// described in mix_word in little-endian order
+pub(crate) const BYTES_PER_WORD: usize = 8;
+
+pub(crate) fn block_to_bytes<B: ByteSize>(block: &[B::Word]) -> Vec<u8> {
+    block.iter().flat_map(|word| B::from_word(*word).to_le_bytes().to_vec()).collect()
+}
+
+pub(crate) fn block_from_bytes<B: ByteSize>(bytes: &[u8], block: &mut [B::Word]) -> Result<(), MixMachineErr> {
+    for (word, chunk) in block.iter_mut().zip(bytes.chunks(BYTES_PER_WORD)) {
+        let mut value_bytes = [0u8; BYTES_PER_WORD];
+        value_bytes.copy_from_slice(chunk);
+        let value = u64::from_le_bytes(value_bytes);
+        if !B::is_valid(value) {
+            return Err(MixMachineErr{message: format!("Invalid word {} in block.", value)});
+        }
+        *word = B::to_word(value);
+    }
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// Disk and drum units, normally attached as units 8 to 15.
+//
+// These are random-access devices with blocks of DISK_BLOCK_SIZE words. As Knuth
+// specifies, IN and OUT transfer the block whose number is in rX, and IOC 0 seeks to
+// that block in advance. Blocks that have never been written read as zeros.
+//
+// The blocks are either kept in a host image file, in the format described in
+// mix_device, or held sparsely in memory.
+
+use mix_device::{Device, BYTES_PER_WORD, block_to_bytes, block_from_bytes};
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+pub const DISK_BLOCK_SIZE: usize = 100;
+const BYTES_PER_BLOCK: u64 = (DISK_BLOCK_SIZE * BYTES_PER_WORD) as u64;
+
+enum DiskStorage {
+    Image(File),
+    Memory(HashMap<u64, Vec<u8>>),
+}
+
+pub struct DiskUnit {
+    storage: DiskStorage,
+    position: u64,  // The block most recently sought, read or written
+}
+
+fn io_error(e: io::Error) -> MixMachineErr {
+    MixMachineErr{message: format!("Disk I/O error: {}", e)}
+}
+
+// The block number held in rX, which must not be negative
+fn block_number<B: ByteSize>(rx: B::Word) -> Result<u64, MixMachineErr> {
+    let n = B::to_signed(B::from_word(rx));
+    if n < 0 {
+        Err(MixMachineErr{message: format!("Invalid block number {}.", n)})
+    } else {
+        Ok(n as u64)
+    }
+}
+
+impl DiskUnit {
+    // A disk kept in the image file at path, which is created if it does not exist
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<DiskUnit, MixMachineErr> {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path).map_err(io_error)?;
+        Ok(DiskUnit{storage: DiskStorage::Image(file), position: 0})
+    }
+
+    // A disk whose blocks are only kept in memory, and are lost when it is dropped
+    pub fn in_memory() -> DiskUnit {
+        DiskUnit{storage: DiskStorage::Memory(HashMap::new()), position: 0}
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    // Block n of the disk, without moving the read/write head
+    pub fn block<B: ByteSize>(&mut self, n: u64) -> Result<Vec<B::Word>, MixMachineErr> {
+        let mut block = vec![B::to_word(0u64); DISK_BLOCK_SIZE];
+        self.read_block::<B>(n, &mut block)?;
+        Ok(block)
+    }
+
+    fn read_block<B: ByteSize>(&mut self, n: u64, block: &mut [B::Word]) -> Result<(), MixMachineErr> {
+        let mut bytes = vec![0u8; BYTES_PER_BLOCK as usize];
+        match self.storage {
+            DiskStorage::Image(ref mut file) => {
+                // Anything beyond the end of the image has never been written
+                let length = file.metadata().map_err(io_error)?.len();
+                let available = length.saturating_sub(n * BYTES_PER_BLOCK).min(BYTES_PER_BLOCK) as usize;
+                file.seek(SeekFrom::Start(n * BYTES_PER_BLOCK)).map_err(io_error)?;
+                file.read_exact(&mut bytes[..available]).map_err(io_error)?;
+            },
+            DiskStorage::Memory(ref blocks) => {
+                if let Some(stored) = blocks.get(&n) {
+                    bytes.copy_from_slice(stored);
+                }
+            },
+        }
+        block_from_bytes::<B>(&bytes, block)
+    }
+}
+
+impl<B: ByteSize> Device<B> for DiskUnit {
+    fn block_size(&self) -> usize {
+        DISK_BLOCK_SIZE
+    }
+
+    fn read(&mut self, block: &mut [B::Word], rx: B::Word) -> Result<(), MixMachineErr> {
+        let n = block_number::<B>(rx)?;
+        self.read_block::<B>(n, block)?;
+        self.position = n;
+        Ok(())
+    }
+
+    fn write(&mut self, block: &[B::Word], rx: B::Word) -> Result<(), MixMachineErr> {
+        let n = block_number::<B>(rx)?;
+        let bytes = block_to_bytes::<B>(block);
+        match self.storage {
+            DiskStorage::Image(ref mut file) => {
+                file.seek(SeekFrom::Start(n * BYTES_PER_BLOCK)).map_err(io_error)?;
+                file.write_all(&bytes).map_err(io_error)?;
+            },
+            DiskStorage::Memory(ref mut blocks) => {
+                blocks.insert(n, bytes);
+            },
+        }
+        self.position = n;
+        Ok(())
+    }
+
+    // Only IOC 0, which seeks to the block in rX, is defined
+    fn control(&mut self, m: i16, rx: B::Word) -> Result<(), MixMachineErr> {
+        if m != 0 {
+            return Err(MixMachineErr{message: format!("Invalid control operation {} for a disk.", m)});
+        }
+        self.position = block_number::<B>(rx)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+// Floating point arithmetic on MIX words, as described in TAOCP 4.2.1.
+//
+// A floating point word has the form  ± e f f f f  where byte 1 is the exponent e,
+// stored in excess-q notation with q half the byte size b, and bytes 2..5 are a
+// fraction f with the radix point at the left. The value represented is  ± f * b^(e - q).
+//
+// Each operation is computed exactly and then rounded to the nearest representable
+// value (ties to even), as in Algorithm 4.2.1N. Operations return the resulting word
+// together with a flag that is true on exponent overflow or underflow; in that case
+// the exponent of the result is only correct modulo the byte size.
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+use mix_word::ByteSize;
+
+const FRACTION_BYTES: u32 = 4;
+
+// The value of an unpacked float is  fraction * b^(exponent - q - FRACTION_BYTES)
+struct Float<B: ByteSize> {
+    negative: bool,
+    exponent: i32,
+    fraction: i128,
+    byte_size: PhantomData<B>,
+}
+
+fn byte<B: ByteSize>() -> i128 {
+    B::BYTE_SIZE as i128
+}
+
+fn excess<B: ByteSize>() -> i32 {
+    (B::BYTE_SIZE / 2) as i32
+}
+
+impl<B: ByteSize> Float<B> {
+    fn unpack(word: u64) -> Float<B> {
+        Float {
+            negative: B::sign(word) != 0,
+            exponent: B::byte(word, 1) as i32,
+            fraction: (B::magnitude(word) % B::power(FRACTION_BYTES)) as i128,
+            byte_size: PhantomData,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.fraction == 0
+    }
+
+    // Scale the fraction so that its leading byte is non-zero. This is exact, although
+    // the exponent may leave the representable range.
+    fn normalize(mut self) -> Float<B> {
+        if self.is_zero() {
+            self.exponent = 0;
+        } else {
+            while self.fraction < byte::<B>().pow(FRACTION_BYTES - 1) {
+                self.fraction *= byte::<B>();
+                self.exponent -= 1;
+            }
+        }
+        self
+    }
+
+    fn scale(&self) -> i32 {
+        self.exponent - excess::<B>() - FRACTION_BYTES as i32
+    }
+}
+
+// Round-to-nearest-even decision when the quotient q has remainder r out of divisor.
+// sticky is true if there are further non-zero digits below the remainder.
+fn round_up(q: i128, r: i128, divisor: i128, sticky: bool) -> bool {
+    let half = divisor / 2;
+    r > half || (r == half && (sticky || q % 2 == 1))
+}
+
+// Produce the floating point word nearest to  ± magnitude * b^scale
+// sticky is true if the exact value is slightly larger than magnitude * b^scale.
+fn round<B: ByteSize>(negative: bool, magnitude: i128, scale: i32, sticky: bool) -> (u64, bool) {
+    let byte = byte::<B>();
+    let sign_bit = if negative { B::SIGN_BIT } else { 0u64 };
+    if magnitude == 0 {
+        return (sign_bit, false);
+    }
+    let mut fraction = magnitude;
+    let mut scale = scale;
+    let mut digits = 0u32;
+    while byte.pow(digits) <= fraction { digits += 1; }
+    if digits > FRACTION_BYTES {
+        let dropped = digits - FRACTION_BYTES;
+        let divisor = byte.pow(dropped);
+        let (q, r) = (fraction / divisor, fraction % divisor);
+        fraction = if round_up(q, r, divisor, sticky) { q + 1 } else { q };
+        scale += dropped as i32;
+        if fraction == byte.pow(FRACTION_BYTES) {
+            // Rounding carried into a new leading byte
+            fraction /= byte;
+            scale += 1;
+        }
+    } else {
+        while fraction < byte.pow(FRACTION_BYTES - 1) {
+            fraction *= byte;
+            scale -= 1;
+        }
+    }
+    let exponent = scale + excess::<B>() + FRACTION_BYTES as i32;
+    let overflow = exponent < 0 || exponent >= byte as i32;
+    let exponent = exponent.rem_euclid(byte as i32) as u64;
+    (sign_bit | (exponent * B::power(FRACTION_BYTES) + fraction as u64), overflow)
+}
+
+fn round_float<B: ByteSize>(x: &Float<B>) -> (u64, bool) {
+    round::<B>(x.negative, x.fraction, x.scale(), false)
+}
+
+pub fn add<B: ByteSize>(u: u64, v: u64) -> (u64, bool) {
+    let u = Float::<B>::unpack(u).normalize();
+    let v = Float::<B>::unpack(v).normalize();
+    if v.is_zero() { return round_float(&u); }
+    if u.is_zero() { return round_float(&v); }
+    let (large, small) = if u.exponent >= v.exponent { (u, v) } else { (v, u) };
+    let shift = (large.exponent - small.exponent) as u32;
+    if shift > FRACTION_BYTES + 2 {
+        // The smaller operand is too small to affect the rounded result
+        return round_float(&large);
+    }
+    let signed = |x: &Float<B>, m: i128| if x.negative { -m } else { m };
+    let sum = signed(&large, large.fraction * byte::<B>().pow(shift)) + signed(&small, small.fraction);
+    round::<B>(sum < 0, sum.abs(), small.scale(), false)
+}
+
+pub fn subtract<B: ByteSize>(u: u64, v: u64) -> (u64, bool) {
+    add::<B>(u, v ^ B::SIGN_BIT)
+}
+
+pub fn multiply<B: ByteSize>(u: u64, v: u64) -> (u64, bool) {
+    let u = Float::<B>::unpack(u).normalize();
+    let v = Float::<B>::unpack(v).normalize();
+    let negative = u.negative != v.negative;
+    if u.is_zero() || v.is_zero() {
+        return round::<B>(negative, 0, 0, false);
+    }
+    round::<B>(negative, u.fraction * v.fraction, u.scale() + v.scale(), false)
+}
+
+// Division by zero leaves u unchanged and reports overflow.
+pub fn divide<B: ByteSize>(u: u64, v: u64) -> (u64, bool) {
+    let u_word = u;
+    let u = Float::<B>::unpack(u).normalize();
+    let v = Float::<B>::unpack(v).normalize();
+    let negative = u.negative != v.negative;
+    if v.is_zero() {
+        return (u_word, true);
+    }
+    if u.is_zero() {
+        return round::<B>(negative, 0, 0, false);
+    }
+    // Two guard digits beyond the fraction, plus a sticky bit, are enough to round correctly
+    let dividend = u.fraction * byte::<B>().pow(FRACTION_BYTES + 2);
+    let (q, r) = (dividend / v.fraction, dividend % v.fraction);
+    round::<B>(negative, q, u.exponent - v.exponent - FRACTION_BYTES as i32 - 2, r != 0)
+}
+
+// FLOT: convert the integer in a to floating point
+pub fn flot<B: ByteSize>(a: u64) -> (u64, bool) {
+    round::<B>(B::sign(a) != 0, B::magnitude(a) as i128, 0, false)
+}
+
+// FIX: convert the floating point number in a to the nearest integer. Overflow occurs
+// if the magnitude does not fit in five bytes, in which case it is taken mod b^5.
+pub fn fix<B: ByteSize>(a: u64) -> (u64, bool) {
+    let byte = byte::<B>();
+    let a = Float::<B>::unpack(a);
+    let sign_bit = if a.negative { B::SIGN_BIT } else { 0u64 };
+    let scale = a.scale();
+    let (magnitude, overflow) = if a.is_zero() {
+        (0i128, false)
+    } else if scale >= 5 {
+        (0i128, true)
+    } else if scale >= 0 {
+        let n = a.fraction * byte.pow(scale as u32);
+        (n % byte.pow(5), n >= byte.pow(5))
+    } else if -scale > FRACTION_BYTES as i32 + 1 {
+        (0i128, false)
+    } else {
+        let divisor = byte.pow((-scale) as u32);
+        let (q, r) = (a.fraction / divisor, a.fraction % divisor);
+        (if round_up(q, r, divisor, false) { q + 1 } else { q }, false)
+    };
+    (sign_bit | magnitude as u64, overflow)
+}
+
+// FCMP: compare u with v using the relations of TAOCP 4.2.2, where epsilon is the
+// magnitude of the given word read as a fraction with the radix point at the left.
+// Less means u is definitely less than v, Equal that they are approximately equal.
+pub fn compare<B: ByteSize>(u: u64, v: u64, epsilon: u64) -> Ordering {
+    let byte = byte::<B>();
+    let u = Float::<B>::unpack(u).normalize();
+    let v = Float::<B>::unpack(v).normalize();
+    if u.is_zero() && v.is_zero() {
+        return Ordering::Equal;
+    }
+    let max_exponent = if u.is_zero() { v.exponent }
+                       else if v.is_zero() { u.exponent }
+                       else { u.exponent.max(v.exponent) };
+    // Work in units of b^(max_exponent - q - 11). The larger operand and the
+    // tolerance are both multiples of b^6 in these units, so an operand more than 7
+    // bytes smaller only matters through its sign, and may be replaced by +/- 1.
+    let scaled = |x: &Float<B>| {
+        let shift = max_exponent - x.exponent;
+        let magnitude = if x.is_zero() { 0 }
+                        else if shift <= 7 { x.fraction * byte.pow((7 - shift) as u32) }
+                        else { 1 };
+        if x.negative { -magnitude } else { magnitude }
+    };
+    let tolerance = B::magnitude(epsilon) as i128 * byte.pow(6);
+    let difference = scaled(&u) - scaled(&v);
+    if -difference > tolerance {
+        Ordering::Less
+    } else if difference > tolerance {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mix_word::Binary;
+
+    fn make_float(positive: bool, e: u64, f: u64) -> u64 {
+        (if positive { 0u64 } else { Binary::SIGN_BIT }) | (e << 24) | f
+    }
+
+    #[test]
+    fn test_round_ties_to_even() {
+        // 0x1000001 needs 25 bits; the dropped byte is exactly one half when it is 32
+        assert_eq!(round::<Binary>(false, (1 << 24) + 32, 0, false), (make_float(true, 37, 1 << 18), false));
+        assert_eq!(round::<Binary>(false, (1 << 24) + 64 + 32, 0, false), (make_float(true, 37, (1 << 18) + 2), false));
+        assert_eq!(round::<Binary>(false, (1 << 24) + 32, 0, true), (make_float(true, 37, (1 << 18) + 1), false));
+    }
+
+    #[test]
+    fn test_round_carry() {
+        // Rounding 64^5 - 1 up gives 64^5, which needs a new exponent
+        assert_eq!(round::<Binary>(false, (1 << 30) - 1, 0, false), (make_float(true, 38, 1 << 18), false));
+    }
+
+    #[test]
+    fn test_round_exponent_range() {
+        assert_eq!(round::<Binary>(false, 1, 31, false), (make_float(true, 0, 1 << 18), true));
+        assert_eq!(round::<Binary>(false, 1, -34, false), (make_float(true, 63, 1 << 18), true));
+        assert_eq!(round::<Binary>(false, 1, 30, false), (make_float(true, 63, 1 << 18), false));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let x = Float::<Binary>::unpack(make_float(false, 33, 5)).normalize();
+        assert_eq!((x.negative, x.exponent, x.fraction), (true, 30, 5 << 18));
+    }
+}
@@ -0,0 +1,279 @@
+// The MIX instruction set, as a single table giving the op code, field, mnemonic,
+// default field, execution time and operand class of every instruction. Decoding,
+// encoding and disassembly are all driven by this table.
+//
+// Some op codes hold several instructions, told apart by their field: JMP, JSJ, ...
+// share op code 39, and FADD is ADD with F = 6. These entries have a fixed field. The
+// other entries accept any field, which is a partial field (L:R), a unit number or a
+// count, depending on the operand class.
+
+use mix_machine::Register;
+use mix_machine::Register::*;
+use mix_operations::{ArithOpType, FloatOpType, IoOpType};
+use mix_instruction_set::OperandClass::*;
+
+#[derive(Clone, Copy)]
+pub enum OperandClass {
+    Load(Register, bool),   // True for the negative loads LDAN, ...
+    Store(Option<Register>),    // None for STZ
+    Arithmetic(ArithOpType),
+    Float(FloatOpType),
+    AddressTransfer(Register),  // The field chooses between INC, DEC, ENT and ENN
+    Comparison(Register),
+    Jump(Option<Register>),     // None for the jumps on the overflow toggle and comparison indicator
+    Shift,                      // The field chooses the shift
+    Move,
+    InputOutput(IoOpType),
+    NoOp,
+    Halt,
+    Num,
+    Char,
+    Interrupt,
+}
+
+// How the field of an instruction is written in MIXAL
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    Partial,    // A partial field (L:R), encoded as 8L + R
+    Number,     // A unit number, or the number of words to move
+    Fixed,      // Part of the instruction itself, and not written
+}
+
+pub struct InstructionDef {
+    pub mnemonic: &'static str,
+    pub op_code: u8,
+    pub default_field: u8,   // The field of a fixed field instruction
+    pub field_is_fixed: bool,
+    pub time: u32,          // In units of u. MOVE takes a further 2u per word moved.
+    pub class: OperandClass,
+}
+
+const fn variable(mnemonic: &'static str, op_code: u8, default_field: u8, time: u32, class: OperandClass) -> InstructionDef {
+    InstructionDef { mnemonic, op_code, default_field, field_is_fixed: false, time, class }
+}
+
+const fn fixed(mnemonic: &'static str, op_code: u8, field: u8, time: u32, class: OperandClass) -> InstructionDef {
+    InstructionDef { mnemonic, op_code, default_field: field, field_is_fixed: true, time, class }
+}
+
+// Execution times are those given by Knuth in TAOCP 1.3.1 (and 4.2.1 for the floating
+// point instructions)
+pub static INSTRUCTIONS: [InstructionDef; 158] = [
+    // Special instructions
+    variable("NOP",   0, 0,  1, NoOp),
+    fixed("NUM",      5, 0, 10, Num),
+    fixed("CHAR",     5, 1, 10, Char),
+    fixed("HLT",      5, 2, 10, Halt),
+    fixed("FLOT",     5, 6,  3, Float(FloatOpType::Flot)),
+    fixed("FIX",      5, 7,  3, Float(FloatOpType::Fix)),
+    fixed("INT",      5, 9,  2, Interrupt),
+    // Arithmetic, with the floating point instructions at F = 6
+    variable("ADD",   1, 5,  2, Arithmetic(ArithOpType::Addition)),
+    variable("SUB",   2, 5,  2, Arithmetic(ArithOpType::Subtraction)),
+    variable("MUL",   3, 5, 10, Arithmetic(ArithOpType::Multiplication)),
+    variable("DIV",   4, 5, 12, Arithmetic(ArithOpType::Division)),
+    fixed("FADD",     1, 6,  4, Float(FloatOpType::Addition)),
+    fixed("FSUB",     2, 6,  4, Float(FloatOpType::Subtraction)),
+    fixed("FMUL",     3, 6,  9, Float(FloatOpType::Multiplication)),
+    fixed("FDIV",     4, 6, 11, Float(FloatOpType::Division)),
+    fixed("FCMP",    56, 6,  4, Float(FloatOpType::Comparison)),
+    // Shifts, including the binary SLB and SRB
+    fixed("SLA",      6, 0,  2, Shift),
+    fixed("SRA",      6, 1,  2, Shift),
+    fixed("SLAX",     6, 2,  2, Shift),
+    fixed("SRAX",     6, 3,  2, Shift),
+    fixed("SLC",      6, 4,  2, Shift),
+    fixed("SRC",      6, 5,  2, Shift),
+    fixed("SLB",      6, 6,  2, Shift),
+    fixed("SRB",      6, 7,  2, Shift),
+    variable("MOVE",  7, 1,  1, Move),
+    // Loads and negative loads
+    variable("LDA",   8, 5,  2, Load(RegA, false)),
+    variable("LD1",   9, 5,  2, Load(RegI1, false)),
+    variable("LD2",  10, 5,  2, Load(RegI2, false)),
+    variable("LD3",  11, 5,  2, Load(RegI3, false)),
+    variable("LD4",  12, 5,  2, Load(RegI4, false)),
+    variable("LD5",  13, 5,  2, Load(RegI5, false)),
+    variable("LD6",  14, 5,  2, Load(RegI6, false)),
+    variable("LDX",  15, 5,  2, Load(RegX, false)),
+    variable("LDAN", 16, 5,  2, Load(RegA, true)),
+    variable("LD1N", 17, 5,  2, Load(RegI1, true)),
+    variable("LD2N", 18, 5,  2, Load(RegI2, true)),
+    variable("LD3N", 19, 5,  2, Load(RegI3, true)),
+    variable("LD4N", 20, 5,  2, Load(RegI4, true)),
+    variable("LD5N", 21, 5,  2, Load(RegI5, true)),
+    variable("LD6N", 22, 5,  2, Load(RegI6, true)),
+    variable("LDXN", 23, 5,  2, Load(RegX, true)),
+    // Stores
+    variable("STA",  24, 5,  2, Store(Some(RegA))),
+    variable("ST1",  25, 5,  2, Store(Some(RegI1))),
+    variable("ST2",  26, 5,  2, Store(Some(RegI2))),
+    variable("ST3",  27, 5,  2, Store(Some(RegI3))),
+    variable("ST4",  28, 5,  2, Store(Some(RegI4))),
+    variable("ST5",  29, 5,  2, Store(Some(RegI5))),
+    variable("ST6",  30, 5,  2, Store(Some(RegI6))),
+    variable("STX",  31, 5,  2, Store(Some(RegX))),
+    variable("STJ",  32, 2,  2, Store(Some(RegJ))),
+    variable("STZ",  33, 5,  2, Store(None)),
+    // Input-output, with the unit number in the field
+    variable("JBUS", 34, 0,  1, InputOutput(IoOpType::JumpBusy)),
+    variable("IOC",  35, 0,  1, InputOutput(IoOpType::Control)),
+    variable("IN",   36, 0,  1, InputOutput(IoOpType::Input)),
+    variable("OUT",  37, 0,  1, InputOutput(IoOpType::Output)),
+    variable("JRED", 38, 0,  1, InputOutput(IoOpType::JumpReady)),
+    // Jumps on the overflow toggle and comparison indicator
+    fixed("JMP",     39, 0,  1, Jump(None)),
+    fixed("JSJ",     39, 1,  1, Jump(None)),
+    fixed("JOV",     39, 2,  1, Jump(None)),
+    fixed("JNOV",    39, 3,  1, Jump(None)),
+    fixed("JL",      39, 4,  1, Jump(None)),
+    fixed("JE",      39, 5,  1, Jump(None)),
+    fixed("JG",      39, 6,  1, Jump(None)),
+    fixed("JGE",     39, 7,  1, Jump(None)),
+    fixed("JNE",     39, 8,  1, Jump(None)),
+    fixed("JLE",     39, 9,  1, Jump(None)),
+    // Jumps on the registers, with the binary JxE and JxO for rA and rX
+    fixed("JAN",     40, 0,  1, Jump(Some(RegA))),
+    fixed("JAZ",     40, 1,  1, Jump(Some(RegA))),
+    fixed("JAP",     40, 2,  1, Jump(Some(RegA))),
+    fixed("JANN",    40, 3,  1, Jump(Some(RegA))),
+    fixed("JANZ",    40, 4,  1, Jump(Some(RegA))),
+    fixed("JANP",    40, 5,  1, Jump(Some(RegA))),
+    fixed("JAE",     40, 6,  1, Jump(Some(RegA))),
+    fixed("JAO",     40, 7,  1, Jump(Some(RegA))),
+    fixed("J1N",     41, 0,  1, Jump(Some(RegI1))),
+    fixed("J1Z",     41, 1,  1, Jump(Some(RegI1))),
+    fixed("J1P",     41, 2,  1, Jump(Some(RegI1))),
+    fixed("J1NN",    41, 3,  1, Jump(Some(RegI1))),
+    fixed("J1NZ",    41, 4,  1, Jump(Some(RegI1))),
+    fixed("J1NP",    41, 5,  1, Jump(Some(RegI1))),
+    fixed("J2N",     42, 0,  1, Jump(Some(RegI2))),
+    fixed("J2Z",     42, 1,  1, Jump(Some(RegI2))),
+    fixed("J2P",     42, 2,  1, Jump(Some(RegI2))),
+    fixed("J2NN",    42, 3,  1, Jump(Some(RegI2))),
+    fixed("J2NZ",    42, 4,  1, Jump(Some(RegI2))),
+    fixed("J2NP",    42, 5,  1, Jump(Some(RegI2))),
+    fixed("J3N",     43, 0,  1, Jump(Some(RegI3))),
+    fixed("J3Z",     43, 1,  1, Jump(Some(RegI3))),
+    fixed("J3P",     43, 2,  1, Jump(Some(RegI3))),
+    fixed("J3NN",    43, 3,  1, Jump(Some(RegI3))),
+    fixed("J3NZ",    43, 4,  1, Jump(Some(RegI3))),
+    fixed("J3NP",    43, 5,  1, Jump(Some(RegI3))),
+    fixed("J4N",     44, 0,  1, Jump(Some(RegI4))),
+    fixed("J4Z",     44, 1,  1, Jump(Some(RegI4))),
+    fixed("J4P",     44, 2,  1, Jump(Some(RegI4))),
+    fixed("J4NN",    44, 3,  1, Jump(Some(RegI4))),
+    fixed("J4NZ",    44, 4,  1, Jump(Some(RegI4))),
+    fixed("J4NP",    44, 5,  1, Jump(Some(RegI4))),
+    fixed("J5N",     45, 0,  1, Jump(Some(RegI5))),
+    fixed("J5Z",     45, 1,  1, Jump(Some(RegI5))),
+    fixed("J5P",     45, 2,  1, Jump(Some(RegI5))),
+    fixed("J5NN",    45, 3,  1, Jump(Some(RegI5))),
+    fixed("J5NZ",    45, 4,  1, Jump(Some(RegI5))),
+    fixed("J5NP",    45, 5,  1, Jump(Some(RegI5))),
+    fixed("J6N",     46, 0,  1, Jump(Some(RegI6))),
+    fixed("J6Z",     46, 1,  1, Jump(Some(RegI6))),
+    fixed("J6P",     46, 2,  1, Jump(Some(RegI6))),
+    fixed("J6NN",    46, 3,  1, Jump(Some(RegI6))),
+    fixed("J6NZ",    46, 4,  1, Jump(Some(RegI6))),
+    fixed("J6NP",    46, 5,  1, Jump(Some(RegI6))),
+    fixed("JXN",     47, 0,  1, Jump(Some(RegX))),
+    fixed("JXZ",     47, 1,  1, Jump(Some(RegX))),
+    fixed("JXP",     47, 2,  1, Jump(Some(RegX))),
+    fixed("JXNN",    47, 3,  1, Jump(Some(RegX))),
+    fixed("JXNZ",    47, 4,  1, Jump(Some(RegX))),
+    fixed("JXNP",    47, 5,  1, Jump(Some(RegX))),
+    fixed("JXE",     47, 6,  1, Jump(Some(RegX))),
+    fixed("JXO",     47, 7,  1, Jump(Some(RegX))),
+    // Address transfer
+    fixed("INCA",    48, 0,  1, AddressTransfer(RegA)),
+    fixed("DECA",    48, 1,  1, AddressTransfer(RegA)),
+    fixed("ENTA",    48, 2,  1, AddressTransfer(RegA)),
+    fixed("ENNA",    48, 3,  1, AddressTransfer(RegA)),
+    fixed("INC1",    49, 0,  1, AddressTransfer(RegI1)),
+    fixed("DEC1",    49, 1,  1, AddressTransfer(RegI1)),
+    fixed("ENT1",    49, 2,  1, AddressTransfer(RegI1)),
+    fixed("ENN1",    49, 3,  1, AddressTransfer(RegI1)),
+    fixed("INC2",    50, 0,  1, AddressTransfer(RegI2)),
+    fixed("DEC2",    50, 1,  1, AddressTransfer(RegI2)),
+    fixed("ENT2",    50, 2,  1, AddressTransfer(RegI2)),
+    fixed("ENN2",    50, 3,  1, AddressTransfer(RegI2)),
+    fixed("INC3",    51, 0,  1, AddressTransfer(RegI3)),
+    fixed("DEC3",    51, 1,  1, AddressTransfer(RegI3)),
+    fixed("ENT3",    51, 2,  1, AddressTransfer(RegI3)),
+    fixed("ENN3",    51, 3,  1, AddressTransfer(RegI3)),
+    fixed("INC4",    52, 0,  1, AddressTransfer(RegI4)),
+    fixed("DEC4",    52, 1,  1, AddressTransfer(RegI4)),
+    fixed("ENT4",    52, 2,  1, AddressTransfer(RegI4)),
+    fixed("ENN4",    52, 3,  1, AddressTransfer(RegI4)),
+    fixed("INC5",    53, 0,  1, AddressTransfer(RegI5)),
+    fixed("DEC5",    53, 1,  1, AddressTransfer(RegI5)),
+    fixed("ENT5",    53, 2,  1, AddressTransfer(RegI5)),
+    fixed("ENN5",    53, 3,  1, AddressTransfer(RegI5)),
+    fixed("INC6",    54, 0,  1, AddressTransfer(RegI6)),
+    fixed("DEC6",    54, 1,  1, AddressTransfer(RegI6)),
+    fixed("ENT6",    54, 2,  1, AddressTransfer(RegI6)),
+    fixed("ENN6",    54, 3,  1, AddressTransfer(RegI6)),
+    fixed("INCX",    55, 0,  1, AddressTransfer(RegX)),
+    fixed("DECX",    55, 1,  1, AddressTransfer(RegX)),
+    fixed("ENTX",    55, 2,  1, AddressTransfer(RegX)),
+    fixed("ENNX",    55, 3,  1, AddressTransfer(RegX)),
+    // Comparison
+    variable("CMPA", 56, 5,  2, Comparison(RegA)),
+    variable("CMP1", 57, 5,  2, Comparison(RegI1)),
+    variable("CMP2", 58, 5,  2, Comparison(RegI2)),
+    variable("CMP3", 59, 5,  2, Comparison(RegI3)),
+    variable("CMP4", 60, 5,  2, Comparison(RegI4)),
+    variable("CMP5", 61, 5,  2, Comparison(RegI5)),
+    variable("CMP6", 62, 5,  2, Comparison(RegI6)),
+    variable("CMPX", 63, 5,  2, Comparison(RegX)),
+];
+
+impl InstructionDef {
+    // The instruction with the given op code and field, preferring a fixed field entry
+    pub fn lookup(op_code: u8, field: u8) -> Option<&'static InstructionDef> {
+        INSTRUCTIONS.iter().find(|def| def.op_code == op_code && def.field_is_fixed && def.default_field == field)
+            .or_else(|| INSTRUCTIONS.iter().find(|def| def.op_code == op_code && !def.field_is_fixed))
+    }
+
+    // True if some instruction has this op code, whatever its field
+    pub fn is_op_code(op_code: u8) -> bool {
+        INSTRUCTIONS.iter().any(|def| def.op_code == op_code)
+    }
+
+    // A partial field (L:R) must have L <= R <= 5. Other fields are checked when the
+    // instruction is executed, since their meaning depends on the machine.
+    pub fn is_valid_field(&self, field: u8) -> bool {
+        match self.field_kind() {
+            FieldKind::Partial => field / 8 <= field % 8 && field % 8 <= 5,
+            FieldKind::Number | FieldKind::Fixed => true,
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<&'static InstructionDef> {
+        INSTRUCTIONS.iter().find(|def| def.mnemonic == mnemonic)
+    }
+
+    pub fn field_kind(&self) -> FieldKind {
+        match self.class {
+            _ if self.field_is_fixed => FieldKind::Fixed,
+            Move | InputOutput(_) | NoOp => FieldKind::Number,
+            _ => FieldKind::Partial,
+        }
+    }
+
+    // SLB, SRB, JxE and JxO, which only exist on a binary MIX
+    pub fn is_binary_extension(&self) -> bool {
+        match self.class {
+            Shift | Jump(Some(_)) => self.default_field >= 6,
+            _ => false,
+        }
+    }
+
+    pub fn execution_time(&self, field: u8) -> u32 {
+        match self.class {
+            Move => self.time + 2 * field as u32,   // Each word is loaded and stored
+            _ => self.time,
+        }
+    }
+}
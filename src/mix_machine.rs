@@ -1,9 +1,23 @@
 use mix_operations::*;
 use mix_operations::Operation::*;
-use std::cmp::min;
-
-const MAX_WORD_VALUE: u32 = (1 << 31) - 1;
-const MEM_SIZE: u16 = 4000;
+use mix_word::{ByteSize, Binary, Decimal};
+use mix_float;
+use mix_device::{Device, MAX_UNIT};
+use mix_instruction_set::{InstructionDef, OperandClass};
+use mix_char::DIGIT_ZERO;
+use mix_trap::{Trap, TrapKind, TrapAction, TrapPolicy};
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+const DEFAULT_MEM_SIZE: u16 = 4000;
+const CONTROL_MEM_SIZE: u16 = 3999;    // Locations -1, ..., -3999, only accessible in control state
+
+// Locations in control memory used by the interrupt facility (TAOCP 1.4.4)
+const SAVED_STATE_LOCATION: i16 = -1;   // -9, ..., -1 hold the registers of the interrupted program
+const INTERVAL_TIMER_LOCATION: i16 = -10;
+const TIMER_INTERRUPT_LOCATION: i16 = -11;
+const INT_INTERRUPT_LOCATION: i16 = -12;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ComparisonState {
@@ -12,97 +26,283 @@ pub enum ComparisonState {
     Equal,
 }
 
+// A MIX machine whose bytes hold B::BYTE_SIZE values. Every register and memory word
+// is held in the u64 format described in mix_word, and converted to B::Word at the
+// public interface. The index registers and rJ only ever use bytes 4 and 5.
 #[allow(non_snake_case)]    // Allow the register names to conform to Knuth's capitalisation
-pub struct MixMachine {
-    register_A: u32,
-    register_X: u32,
-    register_I1: u16,
-    register_I2: u16,
-    register_I3: u16,
-    register_I4: u16,
-    register_I5: u16,
-    register_I6: u16,
-    register_J: u16,
-    program_counter: u16,   // Not strictly specified in MIX, but needed!
+pub struct GenericMixMachine<B: ByteSize> {
+    register_A: u64,
+    register_X: u64,
+    register_I1: u64,
+    register_I2: u64,
+    register_I3: u64,
+    register_I4: u64,
+    register_I5: u64,
+    register_I6: u64,
+    register_J: u64,
+    program_counter: i16,   // Not strictly specified in MIX, but needed! Negative in control state.
     comparison_indicator : ComparisonState,
     overflow_toggle_on: bool,
-    memory: [u32; MEM_SIZE as usize]
+    halted: bool,
+    control_state: bool,
+    pending_interrupts: VecDeque<i16>,   // Locations of interrupts waiting for a return to normal state
+    elapsed_time: u64,      // Total execution time, in units of u
+    last_step_time: u32,
+    memory: Vec<u64>,
+    control_memory: [u64; CONTROL_MEM_SIZE as usize],
+    floating_point_enabled: bool,
+    interrupts_enabled: bool,
+    binary_extensions_enabled: bool,    // SLB, SRB, JxE and JxO
+    io_conflict_checks_enabled: bool,
+    devices: Vec<Option<Box<dyn Device<B>>>>,   // Indexed by unit number
+    device_latencies: Vec<u32>,     // Time in u for which each unit is busy after IN, OUT or IOC
+    busy_until: Vec<u64>,           // Elapsed time at which each unit becomes ready
+    io_buffers: Vec<Option<(i16, usize)>>,  // Start and length of the block last used by IN or OUT on each unit
+    io_wait: u64,                   // Time the IN or OUT being executed has waited for its unit
+    instruction_location: i16,      // Location of the instruction being executed
+    trap_policy: TrapPolicy<B::Word>,
+    byte_size: PhantomData<B>,
+}
+
+// Configuration for a new machine, obtained from MixMachine::builder(). Anything that
+// is not set has the same value as in a machine from MixMachine::new().
+pub struct MixMachineBuilder<B: ByteSize> {
+    memory_size: u16,
+    registers: Vec<(Register, B::Word)>,
+    program_counter: u16,
+    floating_point: bool,
+    interrupts: bool,
+    binary_extensions: bool,
+    io_conflict_checks: bool,
+    trap_policy: TrapPolicy<B::Word>,
 }
 
+// The binary MIX machine, with 6-bit bytes and words stored as u32
+pub type MixMachine = GenericMixMachine<Binary>;
+
+// A decimal MIX machine, with bytes holding 0 to 99 and words stored as u64
+pub type DecimalMixMachine = GenericMixMachine<Decimal>;
+
 #[derive(PartialEq, Debug)]
 pub struct MixMachineErr {
     pub message: String,
 }
 
 #[derive(Clone, Copy)]
-pub enum Register { RegA, RegX, RegI1, RegI2, RegI3, RegI4, RegI5, RegI6, RegJ } 
-
-impl MixMachine {
-    pub fn new() -> MixMachine {
-        MixMachine{
-            register_A: 0u32, register_X: 0u32,
-            register_I1: 0u16, register_I2: 0u16,
-            register_I3: 0u16, register_I4: 0u16,
-            register_I5: 0u16, register_I6: 0u16,
-            program_counter: 0u16,
+pub enum Register { RegA, RegX, RegI1, RegI2, RegI3, RegI4, RegI5, RegI6, RegJ }
+
+impl<B: ByteSize> Default for GenericMixMachine<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: ByteSize> GenericMixMachine<B> {
+    pub fn new() -> GenericMixMachine<B> {
+        GenericMixMachine{
+            register_A: 0u64, register_X: 0u64,
+            register_I1: 0u64, register_I2: 0u64,
+            register_I3: 0u64, register_I4: 0u64,
+            register_I5: 0u64, register_I6: 0u64,
+            program_counter: 0i16,
             overflow_toggle_on: false,
+            halted: false,
+            control_state: false,
+            pending_interrupts: VecDeque::new(),
+            elapsed_time: 0u64,
+            last_step_time: 0u32,
             comparison_indicator : ComparisonState::Less,
-            register_J: 0u16, memory: [0; MEM_SIZE as usize]
+            register_J: 0u64, memory: vec![0; DEFAULT_MEM_SIZE as usize],
+            control_memory: [0; CONTROL_MEM_SIZE as usize],
+            floating_point_enabled: true,
+            interrupts_enabled: true,
+            binary_extensions_enabled: B::BINARY,
+            io_conflict_checks_enabled: false,
+            devices: (0..MAX_UNIT + 1).map(|_| None).collect(),
+            device_latencies: vec![0; MAX_UNIT as usize + 1],
+            busy_until: vec![0; MAX_UNIT as usize + 1],
+            io_buffers: vec![None; MAX_UNIT as usize + 1],
+            io_wait: 0u64,
+            instruction_location: 0,
+            trap_policy: TrapPolicy::Stop,
+            byte_size: PhantomData,
         }
     }
 
-    pub fn poke_memory(&mut self, address: u16, value: u32) -> Result<(), MixMachineErr> {
-        if address >= MEM_SIZE {
-            Err(MixMachineErr{message: format!("Attempt to access invalid memory address {}.", address)})
-        } else if value > MAX_WORD_VALUE {
-            Err(MixMachineErr{message: format!("Attempt to write invalid value {}.", value)})
+    pub fn builder() -> MixMachineBuilder<B> {
+        MixMachineBuilder {
+            memory_size: DEFAULT_MEM_SIZE,
+            registers: Vec::new(),
+            program_counter: 0u16,
+            floating_point: true,
+            interrupts: true,
+            binary_extensions: B::BINARY,
+            io_conflict_checks: false,
+            trap_policy: TrapPolicy::Stop,
+        }
+    }
+
+    // Number of words of ordinary memory, at locations 0 to memory_size() - 1
+    pub fn memory_size(&self) -> u16 {
+        self.memory.len() as u16
+    }
+
+    pub fn poke_memory(&mut self, address: u16, value: B::Word) -> Result<(), MixMachineErr> {
+        self.set_memory_word(address, B::from_word(value))
+    }
+
+    // Continue execution in normal state from the given location
+    pub fn set_program_counter(&mut self, location: u16) -> Result<(), MixMachineErr> {
+        if location >= self.memory_size() {
+            Err(MixMachineErr{message: format!("Program counter {} is outside memory.", location)})
         } else {
-            self.memory[address as usize] = value;
+            self.program_counter = location as i16;
             Ok(())
         }
     }
 
     pub fn poke_address_to_memory(&mut self, address: u16, value: i16) -> Result<(), MixMachineErr> {
-        self.poke_memory(address, MixMachine::i32_to_reg32(value as i32))
+        self.set_memory_word(address, B::from_signed(value as i64))
+    }
+
+    pub fn peek_memory(&self, address: u16) -> Result<B::Word, MixMachineErr> {
+        self.memory_word(address).map(B::to_word)
     }
 
-    pub fn peek_memory(&self, address: u16) -> Result<u32, MixMachineErr> {
-        if address >= MEM_SIZE {
+    // Access to the negative locations -1, ..., -3999 used in control state
+    pub fn poke_control_memory(&mut self, address: i16, value: B::Word) -> Result<(), MixMachineErr> {
+        self.set_control_memory_word(address, B::from_word(value))
+    }
+
+    pub fn peek_control_memory(&self, address: i16) -> Result<B::Word, MixMachineErr> {
+        self.control_memory_word(address).map(B::to_word)
+    }
+
+    fn memory_word(&self, address: u16) -> Result<u64, MixMachineErr> {
+        if address >= self.memory_size() {
             Err(MixMachineErr{message: format!("Attempt to access invalid memory address {}.", address)})
         } else {
             Ok(self.memory[address as usize])
         }
     }
 
-    // Note: This function does not fail. It is up to the user to make sure that
-    //       the value they are passive through is appropriate.
-    pub fn poke_register(&mut self, reg: Register, value: u32) -> Result<(), MixMachineErr> {
-        match reg {
-            Register::RegA  => {self.register_A = value; Ok(())},
-            Register::RegX  => {self.register_X = value; Ok(())},
-            Register::RegI1 => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_I1 = x; Ok(())}),
-            Register::RegI2 => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_I2 = x; Ok(())}),
-            Register::RegI3 => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_I3 = x; Ok(())}),
-            Register::RegI4 => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_I4 = x; Ok(())}),
-            Register::RegI5 => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_I5 = x; Ok(())}),
-            Register::RegI6 => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_I6 = x; Ok(())}),
-            Register::RegJ  => MixMachine::reg32_to_reg16(value).and_then(|x| {self.register_J = x;  Ok(())}),
+    fn set_memory_word(&mut self, address: u16, value: u64) -> Result<(), MixMachineErr> {
+        if address >= self.memory_size() {
+            Err(MixMachineErr{message: format!("Attempt to access invalid memory address {}.", address)})
+        } else if !B::is_valid(value) {
+            Err(MixMachineErr{message: format!("Attempt to write invalid value {}.", value)})
+        } else {
+            self.memory[address as usize] = value;
+            Ok(())
+        }
+    }
+
+    fn control_memory_word(&self, address: i16) -> Result<u64, MixMachineErr> {
+        if address >= 0 || address < -(CONTROL_MEM_SIZE as i16) {
+            Err(MixMachineErr{message: format!("Attempt to access invalid control memory address {}.", address)})
+        } else {
+            Ok(self.control_memory[(-address - 1) as usize])
+        }
+    }
 
+    fn set_control_memory_word(&mut self, address: i16, value: u64) -> Result<(), MixMachineErr> {
+        if address >= 0 || address < -(CONTROL_MEM_SIZE as i16) {
+            Err(MixMachineErr{message: format!("Attempt to access invalid control memory address {}.", address)})
+        } else if !B::is_valid(value) {
+            Err(MixMachineErr{message: format!("Attempt to write invalid value {}.", value)})
+        } else {
+            self.control_memory[(-address - 1) as usize] = value;
+            Ok(())
+        }
+    }
+
+    // Memory access on behalf of the running program. Negative locations are only
+    // available in control state.
+    fn read_memory(&self, address: i16) -> Result<u64, MixMachineErr> {
+        self.check_io_conflict(address)?;
+        if address >= 0 {
+            self.memory_word(address as u16)
+        } else if self.control_state {
+            self.control_memory_word(address)
+        } else {
+            Err(MixMachineErr{message: format!("Attempt to access control memory address {} in normal state.", address)})
         }
     }
 
-    pub fn peek_register(&self, reg: Register) -> Result<u32, MixMachineErr> {
-        Ok(match reg {
+    fn write_memory(&mut self, address: i16, value: u64) -> Result<(), MixMachineErr> {
+        self.check_io_conflict(address)?;
+        if address >= 0 {
+            self.set_memory_word(address as u16, value)
+        } else if self.control_state {
+            self.set_control_memory_word(address, value)
+        } else {
+            Err(MixMachineErr{message: format!("Attempt to access control memory address {} in normal state.", address)})
+        }
+    }
+
+    // When I/O conflict checks are enabled, a program may not touch the block of an IN or
+    // OUT while the unit is still busy with it. Busy units are judged at the start of
+    // the instruction, or for IN and OUT once they have waited for their own unit.
+    fn check_io_conflict(&self, address: i16) -> Result<(), MixMachineErr> {
+        if !self.io_conflict_checks_enabled {
+            return Ok(());
+        }
+        for (unit, buffer) in self.io_buffers.iter().enumerate() {
+            if let Some((start, length)) = *buffer {
+                if address >= start && (address as i32) < start as i32 + length as i32
+                        && self.unit_busy_at(unit as u8, self.elapsed_time + self.io_wait) {
+                    return Err(MixMachineErr{message: format!("I/O conflict: instruction at {} accesses location {} in the block of busy unit {}.",
+                                                              self.instruction_location, address, unit)});
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Note: This function only fails if an index register or rJ is given a value with
+    //       any of bytes 1, 2 and 3 non-zero. It is up to the user to make sure that
+    //       the value they are passing through is otherwise appropriate.
+    pub fn poke_register(&mut self, reg: Register, value: B::Word) -> Result<(), MixMachineErr> {
+        self.set_register(reg, B::from_word(value))
+    }
+
+    pub fn peek_register(&self, reg: Register) -> Result<B::Word, MixMachineErr> {
+        Ok(B::to_word(self.register(reg)))
+    }
+
+    fn register(&self, reg: Register) -> u64 {
+        match reg {
             Register::RegA  => self.register_A,
             Register::RegX  => self.register_X,
-            Register::RegI1 => MixMachine::reg16_to_reg32(self.register_I1),
-            Register::RegI2 => MixMachine::reg16_to_reg32(self.register_I2),
-            Register::RegI3 => MixMachine::reg16_to_reg32(self.register_I3),
-            Register::RegI4 => MixMachine::reg16_to_reg32(self.register_I4),
-            Register::RegI5 => MixMachine::reg16_to_reg32(self.register_I5),
-            Register::RegI6 => MixMachine::reg16_to_reg32(self.register_I6),
-            Register::RegJ  => MixMachine::reg16_to_reg32(self.register_J ),
-        })
+            Register::RegI1 => self.register_I1,
+            Register::RegI2 => self.register_I2,
+            Register::RegI3 => self.register_I3,
+            Register::RegI4 => self.register_I4,
+            Register::RegI5 => self.register_I5,
+            Register::RegI6 => self.register_I6,
+            Register::RegJ  => self.register_J,
+        }
+    }
+
+    fn set_register(&mut self, reg: Register, value: u64) -> Result<(), MixMachineErr> {
+        let target = match reg {
+            Register::RegA  => { self.register_A = value; return Ok(()) },
+            Register::RegX  => { self.register_X = value; return Ok(()) },
+            Register::RegI1 => &mut self.register_I1,
+            Register::RegI2 => &mut self.register_I2,
+            Register::RegI3 => &mut self.register_I3,
+            Register::RegI4 => &mut self.register_I4,
+            Register::RegI5 => &mut self.register_I5,
+            Register::RegI6 => &mut self.register_I6,
+            Register::RegJ  => &mut self.register_J,
+        };
+        // The index registers and rJ only hold the sign and bytes 4 and 5
+        if B::magnitude(value) >= B::power(2) {
+            Err(MixMachineErr{message: String::from("Attempt to poke two byte register with value where bytes 1, 2 and 3 are not all zero.")})
+        } else {
+            *target = value;
+            Ok(())
+        }
     }
 
     pub fn peek_comparison_indicator(&self) -> Result<ComparisonState, MixMachineErr> {
@@ -128,52 +328,118 @@ impl MixMachine {
         Ok(())
     }
 
-    // For if we want to load a register from a memory value
-    fn reg32_to_reg16 (reg32: u32) -> Result<u16, MixMachineErr> {
-        if (reg32 >> 12) % (1u32 << 18) != 0u32 {
-            Err(MixMachineErr{message: format!("Attempt to poke 16 bit register with 32-bit value were bytes 1, 2 and 3 are not all zero.")})
-        } else {
-            Ok(((reg32 >> 18) + (reg32 % (1u32 << 12))) as u16)
+    // True once a HLT instruction has been executed
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    // Total time taken by the instructions executed so far, in units of u
+    pub fn elapsed_time(&self) -> u64 {
+        self.elapsed_time
+    }
+
+    // Time taken by the most recently executed instruction, in units of u
+    pub fn last_step_time(&self) -> u32 {
+        self.last_step_time
+    }
+
+    // Units that are busy stay busy for the same length of time after the reset
+    pub fn reset_elapsed_time(&mut self) {
+        for busy_until in self.busy_until.iter_mut() {
+            *busy_until = busy_until.saturating_sub(self.elapsed_time);
         }
+        self.elapsed_time = 0u64;
+        self.last_step_time = 0u32;
     }
 
-    // For if we want to take a reg16 (I1, ..., I6 or J) and put it into
-    // memory or a reg32.
-    fn reg16_to_reg32 (reg16: u16) -> u32 {
-        ((reg16 as u32) % (1u32 << 12)) + (((reg16 as u32) & (1u32 << 12)) << 18)
+    pub fn is_in_control_state(&self) -> bool {
+        self.control_state
     }
 
-    // Convert the u32 mix storage format (which supports +/-0) into a signed u32
-    // This is helpful for address offset calculations
-    fn reg32_to_i32 (reg32: u32) -> i32 {
-        if reg32 & (1u32 << 30) == 0 {
-            (reg32 % (1u32 << 30)) as i32
+    // Attach a device to the given unit, replacing any device already there
+    pub fn attach_device(&mut self, unit: u8, device: Box<dyn Device<B>>) -> Result<(), MixMachineErr> {
+        if unit > MAX_UNIT {
+            Err(MixMachineErr{message: format!("Invalid unit number {}.", unit)})
         } else {
-            -1i32*((reg32 % (1u32 << 30)) as i32)
+            self.device_latencies[unit as usize] = device.latency();
+            self.busy_until[unit as usize] = 0;
+            self.devices[unit as usize] = Some(device);
+            Ok(())
         }
     }
 
-    pub fn i32_to_reg32 (value: i32) -> u32 {
-        if value >= 0 {
-            value as u32
+    // Remove the device attached to unit, returning it
+    pub fn detach_device(&mut self, unit: u8) -> Result<Option<Box<dyn Device<B>>>, MixMachineErr> {
+        if unit > MAX_UNIT {
+            Err(MixMachineErr{message: format!("Invalid unit number {}.", unit)})
         } else {
-            value.abs() as u32 + (1u32 << 30)
+            // A device attached here later starts with the unit ready and no block in use
+            self.busy_until[unit as usize] = 0;
+            self.io_buffers[unit as usize] = None;
+            Ok(self.devices[unit as usize].take())
         }
     }
 
-    fn compute_indexed_address(&self, address: i16, index_spec: u8) -> Result<i16, MixMachineErr> {
-        match index_spec {
-            0 => Ok(address),
-            1 => self.peek_register(Register::RegI1).map(|x| (MixMachine::reg32_to_i32(x) as i16) + address),
-            2 => self.peek_register(Register::RegI2).map(|x| (MixMachine::reg32_to_i32(x) as i16) + address),
-            3 => self.peek_register(Register::RegI3).map(|x| (MixMachine::reg32_to_i32(x) as i16) + address),
-            4 => self.peek_register(Register::RegI4).map(|x| (MixMachine::reg32_to_i32(x) as i16) + address),
-            5 => self.peek_register(Register::RegI5).map(|x| (MixMachine::reg32_to_i32(x) as i16) + address),
-            6 => self.peek_register(Register::RegI6).map(|x| (MixMachine::reg32_to_i32(x) as i16) + address),
-            _ => Err(MixMachineErr{message: format!("Invalid index_spec for computing effective address: {}", index_spec)}),
+    pub fn has_device(&self, unit: u8) -> bool {
+        matches!(self.devices.get(unit as usize), Some(&Some(_)))
+    }
+
+    // Override the latency of the device attached to unit
+    pub fn set_device_latency(&mut self, unit: u8, latency: u32) -> Result<(), MixMachineErr> {
+        self.device(unit)?;
+        self.device_latencies[unit as usize] = latency;
+        Ok(())
+    }
+
+    // True while the unit is busy with an earlier operation, or the device itself
+    // reports that it is busy
+    pub fn is_unit_busy(&self, unit: u8) -> Result<bool, MixMachineErr> {
+        self.device(unit)?;
+        Ok(self.unit_busy_at(unit, self.elapsed_time))
+    }
+
+    fn unit_busy_at(&self, unit: u8, time: u64) -> bool {
+        matches!(self.devices.get(unit as usize), Some(Some(device)) if device.is_busy())
+            || self.busy_until[unit as usize] > time
+    }
+
+    // What to do on an illegal instruction
+    pub fn set_trap_policy(&mut self, policy: TrapPolicy<B::Word>) {
+        self.trap_policy = policy;
+    }
+
+    fn device(&self, unit: u8) -> Result<&dyn Device<B>, MixMachineErr> {
+        match self.devices.get(unit as usize) {
+            Some(Some(device)) => Ok(device.as_ref()),
+            _ => Err(MixMachineErr{message: format!("No device attached to unit {}.", unit)}),
+        }
+    }
+
+    fn device_mut(&mut self, unit: u8) -> Result<&mut Box<dyn Device<B>>, MixMachineErr> {
+        match self.devices.get_mut(unit as usize) {
+            Some(&mut Some(ref mut device)) => Ok(device),
+            _ => Err(MixMachineErr{message: format!("No device attached to unit {}.", unit)}),
         }
     }
 
+    pub fn i32_to_reg32 (value: i32) -> B::Word {
+        B::to_word(B::from_signed(value as i64))
+    }
+
+    fn compute_indexed_address(&self, address: i16, index_spec: u8) -> Result<i16, MixMachineErr> {
+        let index_register = match index_spec {
+            0 => return Ok(address),
+            1 => Register::RegI1,
+            2 => Register::RegI2,
+            3 => Register::RegI3,
+            4 => Register::RegI4,
+            5 => Register::RegI5,
+            6 => Register::RegI6,
+            _ => return Err(MixMachineErr{message: format!("Invalid index_spec for computing effective address: {}", index_spec)}),
+        };
+        Ok(B::to_signed(self.register(index_register)) as i16 + address)
+    }
+
     fn compute_effective_address(&self, address: i16, index_spec: u8) -> Result<u16, MixMachineErr> {
         self.compute_indexed_address(address, index_spec).and_then(|addr| {
             if addr >= 0 {
@@ -184,23 +450,35 @@ impl MixMachine {
         })
     }
 
-    fn compute_address_to_enter(&self, address: i16, index_spec: u8, negative_address: bool) -> Result<u32, MixMachineErr> {
+    // As compute_effective_address, but for instructions that access memory, and so may
+    // refer to the negative locations in control state
+    fn compute_memory_address(&self, address: i16, index_spec: u8) -> Result<i16, MixMachineErr> {
+        self.compute_indexed_address(address, index_spec).and_then(|addr| {
+            if addr >= 0 || (self.control_state && addr >= -(CONTROL_MEM_SIZE as i16)) {
+                Ok(addr)
+            } else {
+                Err(MixMachineErr{message: format!("Computed negative effective address: {}", addr)})
+            }
+        })
+    }
+
+    fn compute_address_to_enter(&self, address: i16, index_spec: u8, negative_address: bool) -> Result<u64, MixMachineErr> {
         self.compute_indexed_address(address, index_spec).map(|addr| {
             if addr == 0 {
                 // In this case, we need to retain the sign of the index_spec to enter
-                if negative_address { (1u32 << 30) } else { 0u32 }
+                if negative_address { B::SIGN_BIT } else { 0u64 }
             } else {
                 // Otherwise, we use the sign of the actual result
-                if addr <= 0 { addr.abs() as u32 | (1u32 << 30) } else { addr.abs() as u32 }
+                B::from_signed(addr as i64)
             }
         })
     }
 
-    fn negate_value(value: u32) -> u32 {
-        value ^ (1<<30)
+    fn negate_value(value: u64) -> u64 {
+        value ^ B::SIGN_BIT
     }
 
-    fn truncate_to_field(value: u32, field: u8) -> Result<u32, MixMachineErr> {
+    fn truncate_to_field(value: u64, field: u8) -> Result<u64, MixMachineErr> {
         let left  = field / 8;
         let right = field % 8;
         if right > 5 {
@@ -210,19 +488,18 @@ impl MixMachine {
         } else if left > right {
             Err(MixMachineErr{message: format!("Field specification {} has L={}, R={}. Must have L<=R.", field, left, right)})
         } else {
-            let (left_byte, sign) = if left == 0 { (1, value & (1u32<<30)) } else { (left, 0u32) };
+            let (left_byte, sign) = if left == 0 { (1, B::sign(value)) } else { (left, 0u64) };
             let right_byte = if right == 0 { 1 } else { right };
             let bytes_out = if right == 0 {
-                0u32
+                0u64
             } else {
-                let bytes_in = value % (1u32<<30);
-                (bytes_in >> (6*(5 - right_byte))) % (1 << (6*(right_byte - left_byte + 1)))
+                (B::magnitude(value) / B::power((5 - right_byte) as u32)) % B::power((right_byte - left_byte + 1) as u32)
             };
             Ok(bytes_out + sign)
         }
     }
 
-    fn embed_from_field(value_to_write: u32, value_to_overwrite: u32, field: u8) -> Result<u32, MixMachineErr> {
+    pub(crate) fn embed_from_field(value_to_write: u64, value_to_overwrite: u64, field: u8) -> Result<u64, MixMachineErr> {
         let left  = field / 8;
         let right = field % 8;
         if right > 5 {
@@ -234,36 +511,37 @@ impl MixMachine {
         } else {
             if left == 0 && right == 0 {
                 // Modify only the sign bit
-                Ok((value_to_overwrite & ((1u32 << 30) - 1)) | (value_to_write & (1u32 << 30)))
+                Ok(B::magnitude(value_to_overwrite) | B::sign(value_to_write))
             } else {
                 // This construct is to avoid code duplication
-                let (left, sign_bit) = 
+                let (left, sign_bit) =
                     if left == 0 {
                         // Modify both the sign bit and some of the bytes
                         let left = 1;   // Shadow with the internal value of the left bit
-                        let sign_bit = value_to_write & (1u32 << 30);
+                        let sign_bit = B::sign(value_to_write);
                         (left, sign_bit)
                     } else {
-                        let sign_bit = value_to_overwrite & (1u32 << 30);
+                        let sign_bit = B::sign(value_to_overwrite);
                         (left, sign_bit)
                     };
-                let mask1 = ((1u32 << 30) - 1) ^ (((1u32 << 6*(right - left + 1)) - 1) << 6 * (5 - right));
-                let masked_value_to_overwrite = value_to_overwrite & mask1;
-                let mask2 = (1u32 << 6*(right - left + 1)) - 1;
-                let masked_value_to_write = (value_to_write & mask2) << 6 * (5 - right);
-                Ok(sign_bit | masked_value_to_overwrite | masked_value_to_write)
+                let field_size = B::power((right - left + 1) as u32);
+                let field_position = B::power((5 - right) as u32);
+                let old_field = (B::magnitude(value_to_overwrite) / field_position) % field_size;
+                let masked_value_to_overwrite = B::magnitude(value_to_overwrite) - old_field * field_position;
+                let masked_value_to_write = (B::magnitude(value_to_write) % field_size) * field_position;
+                Ok(sign_bit | (masked_value_to_overwrite + masked_value_to_write))
             }
         }
     }
 
     fn execute_load_op(&mut self, op: &LoadOp) -> Result<(), MixMachineErr> {
-        self.compute_effective_address(op.address, op.index_spec).and_then(|effective_address| {
-            self.peek_memory(effective_address).and_then(|contents| {
-                MixMachine::truncate_to_field(contents, op.field).and_then(|trunc_cont| {
+        self.compute_memory_address(op.address, op.index_spec).and_then(|effective_address| {
+            self.read_memory(effective_address).and_then(|contents| {
+                Self::truncate_to_field(contents, op.field).and_then(|trunc_cont| {
                     if op.negative {
-                        self.poke_register(op.register, MixMachine::negate_value(trunc_cont))
+                        self.set_register(op.register, Self::negate_value(trunc_cont))
                     } else {
-                        self.poke_register(op.register, trunc_cont)
+                        self.set_register(op.register, trunc_cont)
                     }
                 })
             })
@@ -271,24 +549,23 @@ impl MixMachine {
     }
 
     fn execute_store_op(&mut self, op: &StoreOp) -> Result<(), MixMachineErr> {
-        self.compute_effective_address(op.address, op.index_spec).and_then(|effective_address| {
-            match op.register {
-                Some(reg) => self.peek_register(reg),
-                None      => Ok(0u32),
-            }.and_then(|value_to_load| {
-                self.peek_memory(effective_address).and_then(|value_to_overwrite| {
-                    MixMachine::embed_from_field(value_to_load, value_to_overwrite, op.field).and_then(|value_to_load| {
-                        self.poke_memory(effective_address, value_to_load)
-                    })
+        self.compute_memory_address(op.address, op.index_spec).and_then(|effective_address| {
+            let value_to_load = match op.register {
+                Some(reg) => self.register(reg),
+                None      => 0u64,
+            };
+            self.read_memory(effective_address).and_then(|value_to_overwrite| {
+                Self::embed_from_field(value_to_load, value_to_overwrite, op.field).and_then(|value_to_load| {
+                    self.write_memory(effective_address, value_to_load)
                 })
             })
         })
     }
 
     fn execute_arithmetic_op(&mut self, op: &ArithOp) -> Result<(), MixMachineErr> {
-        self.compute_effective_address(op.address, op.index_spec).and_then(|effective_address| {
-            self.peek_memory(effective_address).and_then(|contents| {
-                MixMachine::truncate_to_field(contents, op.field).and_then(|v| {
+        self.compute_memory_address(op.address, op.index_spec).and_then(|effective_address| {
+            self.read_memory(effective_address).and_then(|contents| {
+                Self::truncate_to_field(contents, op.field).and_then(|v| {
                     match op.op_type {
                         ArithOpType::Addition       => self.execute_addition(v),
                         ArithOpType::Subtraction    => self.execute_subtraction(v),
@@ -300,101 +577,120 @@ impl MixMachine {
         })
     }
 
-    fn execute_subtraction(&mut self, v: u32) -> Result<(), MixMachineErr> {
-        self.execute_addition(v ^ (1u32 << 30)) // Flip the sign of v and perform addition
+    fn execute_subtraction(&mut self, v: u64) -> Result<(), MixMachineErr> {
+        self.execute_addition(Self::negate_value(v)) // Flip the sign of v and perform addition
     }
 
     // Take the truncated memory contents and perform the addition into register A
-    fn execute_addition(&mut self, v: u32) -> Result<(), MixMachineErr> {
+    fn execute_addition(&mut self, v: u64) -> Result<(), MixMachineErr> {
         self.execute_addition_general(Register::RegA, v)
     }
 
     // General addition function - needed to support the INCX, INCI1, ..., DECX, ... operations
-    fn execute_addition_general(&mut self, target_reg : Register , v: u32) -> Result<(), MixMachineErr> {
-        self.peek_register(target_reg).and_then(|a| {
-            let signed_a = if a & (1u32 << 30) == 0u32 { a as i32 } else { -1i32*((a - (1u32 << 30)) as i32) };
-            let signed_v = if v & (1u32 << 30) == 0u32 { v as i32 } else { -1i32*((v - (1u32 << 30)) as i32) };
-            let signed_result = signed_a + signed_v;    // This calculation can't actually overflow, assuming valid mix registers were passed in
-            // Different handling of overflow - overflow on rA or rX sets the toggle,
-            // but on I1, ... I6 the behaviour is undefined.
-            (match target_reg {
-                Register::RegA | Register::RegX => {
-                    (if signed_result >= (1i32 << 30) || signed_result <= -1i32*(1i32 << 30)
-                        { self.set_overflow_toggle() }
-                     else { Ok(()) })
-                },
-                _ => {
-                    (if signed_result >= (1i32 << 12) || signed_result <= -1i32*(1i32 << 12)
-                        { Err(MixMachineErr { message : String::from("Overflow on inc or dec resulted in undefined behaviour!")}) }
-                    else { Ok(()) })
-                },
-            }).and_then(|_| {
-                let result = if signed_result >= 0i32 {
-                    // Ensure that the sign bit is cleared in case of (mix) overflow
-                    (signed_result as u32) & ((1u32 << 30) - 1u32)
-                } else {
-                    // Ensure that the sign bit is set in case of (mix) overflow
-                    (((-1i32*signed_result) as u32)& ((1u32 << 30) - 1u32)) | (1u32 << 30)
-                };
-                self.poke_register(target_reg, result)
-            })
-        })
+    fn execute_addition_general(&mut self, target_reg : Register , v: u64) -> Result<(), MixMachineErr> {
+        let a = self.register(target_reg);
+        let signed_result = B::to_signed(a) + B::to_signed(v);    // This calculation can't actually overflow, assuming valid mix registers were passed in
+        // Different handling of overflow - overflow on rA or rX sets the toggle,
+        // but on I1, ... I6 the behaviour is undefined.
+        let limit = match target_reg {
+            Register::RegA | Register::RegX => {
+                if signed_result.unsigned_abs() >= B::power(5) { self.set_overflow_toggle()?; }
+                B::power(5)
+            },
+            _ => {
+                if signed_result.unsigned_abs() >= B::power(2) {
+                    return Err(MixMachineErr { message : String::from("Overflow on inc or dec resulted in undefined behaviour!")});
+                }
+                B::power(2)
+            },
+        };
+        // The sign is that of the sum, even in case of (mix) overflow
+        let magnitude = signed_result.unsigned_abs() % limit;
+        let result = if signed_result >= 0 { magnitude } else { magnitude | B::SIGN_BIT };
+        self.set_register(target_reg, result)
     }
 
-    fn execute_multiplication(&mut self, v: u32) -> Result<(), MixMachineErr> {
-        self.peek_register(Register::RegA).and_then(|a| {
-            let sign_bit = (a & (1u32 << 30)) ^ (v & (1u32 << 30));
-            let product_magnitude = ((a as u64) & ((1u64 << 30) - 1)) * ((v as u64) & ((1u64 << 30) - 1));
-            let lower_part_of_result = sign_bit | ((product_magnitude & ((1u64 << 30) - 1)) as u32);
-            let upper_part_of_result = sign_bit | (((product_magnitude >> 30) & ((1u64 << 30) - 1)) as u32);
-            self.poke_register(Register::RegX, lower_part_of_result).and_then(|_| {
-                self.poke_register(Register::RegA, upper_part_of_result)
-            })
-        })
+    fn execute_multiplication(&mut self, v: u64) -> Result<(), MixMachineErr> {
+        let a = self.register(Register::RegA);
+        let sign_bit = B::sign(a) ^ B::sign(v);
+        let product_magnitude = B::magnitude(a) as u128 * B::magnitude(v) as u128;
+        let word_size = B::power(5) as u128;
+        let lower_part_of_result = sign_bit | (product_magnitude % word_size) as u64;
+        let upper_part_of_result = sign_bit | (product_magnitude / word_size) as u64;
+        self.set_register(Register::RegX, lower_part_of_result)?;
+        self.set_register(Register::RegA, upper_part_of_result)
     }
 
-    fn execute_division(&mut self, v: u32) -> Result<(), MixMachineErr> {
-        self.peek_register(Register::RegA).and_then(|a| {
-            self.peek_register(Register::RegX).and_then(|x| {
-                let a_magnitude = a & ((1u32 << 30) - 1);
-                let x_magnitude = x & ((1u32 << 30) - 1);
-                let sign_a = a & (1u32 << 30);
-                let sign_v = v & (1u32 << 30);
-                let v_magnitude = (v & ((1u32 << 30) - 1)) as u64;
-                let dividend_magnitude = ((a_magnitude as u64) << 30) | (x_magnitude as u64);
-                let quotient_large = dividend_magnitude / v_magnitude;
-                if quotient_large >= 1u64 << 30 {
-                    self.set_overflow_toggle()
-                } else {
-                    let quotient = (sign_a ^ sign_v) + (quotient_large as u32);
-                    let remainder = sign_a + ((dividend_magnitude % v_magnitude) as u32);
-                    self.poke_register(Register::RegA, quotient).and_then(|_| {
-                        self.poke_register(Register::RegX, remainder)
-                    })
-                }
-            })
-        })
+    // Division by zero, or a quotient that needs more than five bytes, sets the overflow
+    // toggle and leaves rA and rX unchanged
+    fn execute_division(&mut self, v: u64) -> Result<(), MixMachineErr> {
+        let a = self.register(Register::RegA);
+        let x = self.register(Register::RegX);
+        let word_size = B::power(5) as u128;
+        let v_magnitude = B::magnitude(v) as u128;
+        let dividend_magnitude = B::magnitude(a) as u128 * word_size + B::magnitude(x) as u128;
+        if v_magnitude == 0 || dividend_magnitude / v_magnitude >= word_size {
+            self.set_overflow_toggle()
+        } else {
+            let quotient = (B::sign(a) ^ B::sign(v)) | (dividend_magnitude / v_magnitude) as u64;
+            let remainder = B::sign(a) | (dividend_magnitude % v_magnitude) as u64;
+            self.set_register(Register::RegA, quotient)?;
+            self.set_register(Register::RegX, remainder)
+        }
+    }
+
+    fn execute_float_op(&mut self, op: &FloatOp) -> Result<(), MixMachineErr> {
+        let a = self.register(Register::RegA);
+        // FLOT and FIX operate on rA alone
+        let v = match op.op_type {
+            FloatOpType::Flot | FloatOpType::Fix => 0u64,
+            _ => {
+                let effective_address = self.compute_memory_address(op.address, op.index_spec)?;
+                self.read_memory(effective_address)?
+            },
+        };
+        let (result, overflow) = match op.op_type {
+            FloatOpType::Addition       => mix_float::add::<B>(a, v),
+            FloatOpType::Subtraction    => mix_float::subtract::<B>(a, v),
+            FloatOpType::Multiplication => mix_float::multiply::<B>(a, v),
+            FloatOpType::Division       => mix_float::divide::<B>(a, v),
+            FloatOpType::Flot           => mix_float::flot::<B>(a),
+            FloatOpType::Fix            => mix_float::fix::<B>(a),
+            FloatOpType::Comparison     => {
+                // The tolerance for FCMP is taken from location 0
+                let epsilon = self.memory_word(0)?;
+                return self.poke_comparison_indicator(match mix_float::compare::<B>(a, v, epsilon) {
+                    Ordering::Less    => ComparisonState::Less,
+                    Ordering::Equal   => ComparisonState::Equal,
+                    Ordering::Greater => ComparisonState::Greater,
+                });
+            },
+        };
+        if overflow {
+            self.set_overflow_toggle()?;
+        }
+        self.set_register(Register::RegA, result)
     }
 
     fn execute_address_transfer(&mut self, op: &AddressOp) -> Result<(), MixMachineErr> {
         self.compute_address_to_enter(op.address, op.index_spec, op.negative_address).and_then(|addr| {
-            let addr = if op.negate_value { addr + (1u32 << 30) } else { addr };
+            let addr = if op.negate_value { Self::negate_value(addr) } else { addr };
             if op.increase {
                 self.execute_addition_general(op.register, addr)
             } else {
-                self.poke_register(op.register, addr)
+                self.set_register(op.register, addr)
             }
         })
     }
 
     fn execute_comparison_op(&mut self, op: &CompOp) -> Result<(), MixMachineErr> {
-        
-        // Note: turning the registers into i32s is fine, since -0 and +0 are treated as Equal
-        let effective_address = self.compute_effective_address(op.address, op.index_spec)?;
-        let contents = self.peek_memory(effective_address)?;
-        let truncated_contents = MixMachine::reg32_to_i32(MixMachine::truncate_to_field(contents, op.field)?);
-        let register_value = self.peek_register(op.register)?;
-        let truncated_register = MixMachine::reg32_to_i32(MixMachine::truncate_to_field(register_value, op.field)?);
+
+        // Note: turning the registers into i64s is fine, since -0 and +0 are treated as Equal
+        let effective_address = self.compute_memory_address(op.address, op.index_spec)?;
+        let contents = self.read_memory(effective_address)?;
+        let truncated_contents = B::to_signed(Self::truncate_to_field(contents, op.field)?);
+        let register_value = self.register(op.register);
+        let truncated_register = B::to_signed(Self::truncate_to_field(register_value, op.field)?);
 
         if truncated_register < truncated_contents {
             self.poke_comparison_indicator(ComparisonState::Less)
@@ -406,7 +702,7 @@ impl MixMachine {
     }
 
     fn execute_jump_op(&mut self, op: &JumpOp) -> Result<(), MixMachineErr> {
-        let target = self.compute_effective_address(op.address, op.index_spec)?;
+        let target = self.compute_memory_address(op.address, op.index_spec)?;
         // Easier if we do this - lets us move JAN, JAE, etc. into same format as JL, JE, etc.
         let adjusted_field = match op.register {
             None => op.field,
@@ -415,30 +711,35 @@ impl MixMachine {
         let comp_state = match op.register {
             None => self.peek_comparison_indicator(),
             Some(reg) => {
-                let x = self.peek_register(reg).unwrap();
-                if x & ((1u32 << 30) - 1u32) == 0 {
+                let x = self.register(reg);
+                if B::magnitude(x) == 0 {
                     Ok(ComparisonState::Equal)  // Register holds +0 or -0
-                } else if x & (1u32 << 30) != 0 {
+                } else if B::sign(x) != 0 {
                     Ok(ComparisonState::Less)   // Register holds a negative value
                 } else {
                     Ok(ComparisonState::Greater)    // Register holds a positive value
                 }
             },
         };
+        // JxE and JxO (binary MIX only) test the parity of rA or rX
+        let parity_even = match op.register {
+            Some(reg) if adjusted_field == 10 || adjusted_field == 11 => self.register(reg).is_multiple_of(2),
+            _ => false,
+        };
         // Update rJ unless instruction was JSJ
         if adjusted_field != 1 {
             // Recall that we *already* incremented after the instruction fetch
-            let counter = self.program_counter as u32;
-            self.poke_register(Register::RegJ, counter)?
+            let counter = B::from_signed(self.program_counter as i64);
+            self.set_register(Register::RegJ, counter)?
         }
         match adjusted_field {
             0 | 1 => { self.program_counter = target; },
-            2 | 3 => { 
+            2 | 3 => {
                 let overflow = self.peek_overflow_toggle()?;
                 if (adjusted_field == 2 && overflow) || (adjusted_field == 3 && !overflow) {
                     self.program_counter = target;
                 }
-                if overflow { self.clear_overflow_toggle(); }
+                if overflow { self.clear_overflow_toggle()?; }
             },
             4 => { if comp_state == Ok(ComparisonState::Less) {
                     self.program_counter = target; }},
@@ -455,146 +756,479 @@ impl MixMachine {
             9 => { if comp_state == Ok(ComparisonState::Less) || comp_state == Ok(ComparisonState::Equal) {
                        self.program_counter = target;
                    }},
+            10 => { if parity_even {
+                        self.program_counter = target; }},
+            11 => { if !parity_even {
+                        self.program_counter = target; }},
             _ => return Err(MixMachineErr {message : String::from("Invalid field spec in comparison operation")}),
         }
         Ok(())
     }
 
-    fn safe_shift_with_masking(x : u32, shift_bytes : u16, shift_left : bool) -> u32 {
-        if shift_bytes < 5 {
-            let shift_distance = shift_bytes*6;
-            if shift_left {
-                (x << shift_distance) & ((1u32 << 30) - 1u32)
+    // Shift the digits (in base b) of x left by n places, keeping only the lowest `size` digits
+    fn shift_left_digits(x : u128, n : u32, size : u32) -> u128 {
+        let b = B::BYTE_SIZE as u128;
+        if n >= size { 0u128 } else { (x % b.pow(size - n)) * b.pow(n) }
+    }
+
+    fn execute_shift_op(&mut self, op : &ShiftOp) -> Result<(), MixMachineErr> {
+        if op.binary {
+            return self.execute_binary_shift_op(op);
+        }
+        let shift_distance = self.compute_effective_address(op.address, op.index_spec)? as u32;
+        let a = self.register(Register::RegA);
+        let x = self.register(Register::RegX);
+        let b = B::BYTE_SIZE as u128;
+        let word_size = B::power(5) as u128;
+        if !op.use_reg_x {
+            // SLA and SRA
+            let bytes_a = B::magnitude(a) as u128;
+            let new_bytes_a = if op.shift_left {
+                Self::shift_left_digits(bytes_a, shift_distance, 5)
             } else {
-                x >> shift_distance
-            }
-        } else {
-            0u32
+                bytes_a / b.pow(shift_distance.min(5))
+            };
+            return self.set_register(Register::RegA, B::sign(a) | new_bytes_a as u64);
         }
+        // The other shifts treat rA and rX as a single register of ten bytes
+        let bytes = B::magnitude(a) as u128 * word_size + B::magnitude(x) as u128;
+        let new_bytes = if op.circulating_shift {
+            let distance = shift_distance % 10;
+            let distance = if op.shift_left { distance } else { (10 - distance) % 10 };
+            Self::shift_left_digits(bytes, distance, 10) + bytes / b.pow(10 - distance)
+        } else if op.shift_left {
+            Self::shift_left_digits(bytes, shift_distance, 10)
+        } else {
+            bytes / b.pow(shift_distance.min(10))
+        };
+        self.set_register(Register::RegA, B::sign(a) | (new_bytes / word_size) as u64)?;
+        self.set_register(Register::RegX, B::sign(x) | (new_bytes % word_size) as u64)
     }
 
-    fn execute_shift_op(&mut self, op : &ShiftOp) -> Result<(), MixMachineErr> {
-        let reg_a = self.peek_register(Register::RegA).unwrap();
+    // SLB and SRB: shift the 60 bits of rAX, leaving the signs unchanged
+    fn execute_binary_shift_op(&mut self, op : &ShiftOp) -> Result<(), MixMachineErr> {
         let shift_distance = self.compute_effective_address(op.address, op.index_spec)?;
-        let (shift_distance, shift_left) =
-            if op.circulating_shift {
-                // Trick to make sure the we never have to deal with bytes of rA circulating all the
-                // way back into rA again.
-                if shift_distance % 10 == 0 {
-                    (0, true)
-                } else if (shift_distance % 10 - 1) / 5 == 0 {
-                    ((shift_distance % 10), op.shift_left)
-                } else {
-                    ((10 - (shift_distance % 10)), !op.shift_left)
-                }
+        let a = self.register(Register::RegA);
+        let x = self.register(Register::RegX);
+        let bits = (B::magnitude(a) << 30) | B::magnitude(x);
+        let bits = if shift_distance >= 60 {
+            0u64
+        } else if op.shift_left {
+            (bits << shift_distance) & ((1u64 << 60) - 1u64)
+        } else {
+            bits >> shift_distance
+        };
+        self.set_register(Register::RegA, B::sign(a) | (bits >> 30))?;
+        self.set_register(Register::RegX, B::sign(x) | (bits & ((1u64 << 30) - 1u64)))
+    }
+
+    pub fn execute_move_op(&mut self, op : &MoveOp) -> Result<(), MixMachineErr> {
+        let source_start = self.compute_memory_address(op.address, op.index_spec)? as i32;
+        let dest_start = B::to_signed(self.register(Register::RegI1)) as i32;
+        let num_to_move = op.num_to_move as i32;
+        let limit = self.memory_size() as i32;
+        let lower_limit = if self.control_state { -(CONTROL_MEM_SIZE as i32) } else { 0 };
+        if source_start + num_to_move > limit || dest_start + num_to_move > limit || dest_start < lower_limit {
+            Err(MixMachineErr { message : String::from("Attempting to move from invalid address.") })
+        } else {
+            for i in 0..num_to_move {
+                let x = self.read_memory((source_start + i) as i16)?;
+                self.write_memory((dest_start + i) as i16, x)?;
+            }
+            self.set_register(Register::RegI1, B::from_signed((dest_start + num_to_move) as i64))
+        }
+    }
+
+    // Returns the time spent waiting for the unit to finish an earlier operation. IN,
+    // OUT and IOC wait until the unit is ready, and then leave it busy for its latency.
+    // The transfer itself happens at once.
+    fn execute_io_op(&mut self, op : &IoOp) -> Result<u32, MixMachineErr> {
+        // For IOC the effective address is a parameter for the device, and may be negative
+        let address = match op.op_type {
+            IoOpType::Control => self.compute_indexed_address(op.address, op.index_spec)?,
+            _ => self.compute_memory_address(op.address, op.index_spec)?,
+        };
+        let rx = B::to_word(self.register_X);
+        let unit = op.unit;
+        let unit_error = |e: MixMachineErr| MixMachineErr{message: format!("Unit {}: {}", unit, e.message)};
+        let block_size = self.device(unit)?.block_size();
+        let wait = self.busy_until[unit as usize].saturating_sub(self.elapsed_time);
+        // While the device itself reports that it is busy, IN, OUT and IOC keep waiting:
+        // the instruction is tried again by the next step
+        if !matches!(op.op_type, IoOpType::JumpReady | IoOpType::JumpBusy) && self.device(unit)?.is_busy() {
+            self.program_counter = self.instruction_location;
+            return Ok(wait as u32);
+        }
+        self.io_wait = wait;    // The transfer happens after the wait
+        let start_busy = |mix_machine: &mut Self| {
+            mix_machine.busy_until[unit as usize] = mix_machine.elapsed_time + wait + mix_machine.device_latencies[unit as usize] as u64;
+            wait as u32
+        };
+        let check_block = |mix_machine: &Self| {
+            if address as i32 + block_size as i32 > mix_machine.memory_size() as i32 {
+                Err(MixMachineErr{message: format!("Block of {} words at {} does not fit in memory.", block_size, address)})
             } else {
-                (shift_distance, op.shift_left)
-            };
-        //panic!("SD: {}, SL: {}", shift_distance, shift_left);
-        let sign_a = self.peek_register(Register::RegA).unwrap() & (1u32 << 30);
-        let sign_x = self.peek_register(Register::RegX).unwrap() & (1u32 << 30);
-        let bytes_a = self.peek_register(Register::RegA).unwrap() & ((1u32 << 30) - 1u32);
-        let bytes_x = self.peek_register(Register::RegX).unwrap() & ((1u32 << 30) - 1u32);
-        let mut new_bytes_a = MixMachine::safe_shift_with_masking(bytes_a, shift_distance, shift_left);
-        if op.use_reg_x {
-            let mut new_bytes_x = MixMachine::safe_shift_with_masking(bytes_x, shift_distance, shift_left);
-
-            // This part is common to circulating and non-circulating shifts
-            if shift_left {
-                if shift_distance <= 5 {
-                    new_bytes_a = new_bytes_a | MixMachine::safe_shift_with_masking(bytes_x, (5 - shift_distance), false);
-                } else {
-                    new_bytes_a = MixMachine::safe_shift_with_masking(bytes_x, (shift_distance - 5), true);
+                Ok(())
+            }
+        };
+        match op.op_type {
+            IoOpType::Input => {
+                check_block(self)?;
+                self.io_buffers[unit as usize] = None;  // Any earlier block is finished with, after the wait
+                let mut block = vec![B::to_word(0u64); block_size];
+                self.device_mut(unit)?.read(&mut block, rx).map_err(unit_error)?;
+                for (i, word) in block.iter().enumerate() {
+                    self.write_memory(address + i as i16, B::from_word(*word))?;
                 }
-            } else {
-                if shift_distance <= 5 {
-                    new_bytes_x = new_bytes_x | ((bytes_a & ((1u32 << 6*shift_distance) - 1)) << (6*(5 - shift_distance)));
-                } else {
-                    new_bytes_x = bytes_a >> (6*(shift_distance - 5));
+                self.io_buffers[unit as usize] = Some((address, block_size));
+                Ok(start_busy(self))
+            },
+            IoOpType::Output => {
+                check_block(self)?;
+                self.io_buffers[unit as usize] = None;
+                let mut block = Vec::with_capacity(block_size);
+                for i in 0..block_size {
+                    block.push(B::to_word(self.read_memory(address + i as i16)?));
                 }
-            }
-
-            if op.circulating_shift {
-                if shift_left {
-                    new_bytes_x = new_bytes_x | MixMachine::safe_shift_with_masking(bytes_a, (5 - shift_distance), false);
-                } else {
-                    new_bytes_a = new_bytes_a | MixMachine::safe_shift_with_masking(bytes_x, (5 - shift_distance), true);
+                self.device_mut(unit)?.write(&block, rx).map_err(unit_error)?;
+                self.io_buffers[unit as usize] = Some((address, block_size));
+                Ok(start_busy(self))
+            },
+            IoOpType::Control => {
+                self.device_mut(unit)?.control(address, rx).map_err(unit_error)?;
+                Ok(start_busy(self))
+            },
+            IoOpType::JumpReady | IoOpType::JumpBusy => {
+                let busy = self.is_unit_busy(unit)?;
+                if busy == (op.op_type == IoOpType::JumpBusy) {
+                    self.register_J = B::from_signed(self.program_counter as i64);
+                    self.program_counter = address;
                 }
-            }
+                Ok(0)
+            },
+        }
+    }
+
+    // NUM: treat the ten bytes of rA and rX as character coded digits, and set the
+    // magnitude of rA to the number they represent. rX and the sign of rA are unchanged.
+    // On overflow the result is taken modulo b^5 and the overflow toggle is set.
+    fn execute_num(&mut self) -> Result<(), MixMachineErr> {
+        let a = self.register(Register::RegA);
+        let x = self.register(Register::RegX);
+        let mut value = 0u64;
+        for byte in (1..6).map(|i| B::byte(a, i)).chain((1..6).map(|i| B::byte(x, i))) {
+            value = value*10 + byte % 10;
+        }
+        if value >= B::power(5) {
+            self.set_overflow_toggle()?;
+        }
+        self.set_register(Register::RegA, B::sign(a) | (value % B::power(5)))
+    }
 
-            new_bytes_x = new_bytes_x | sign_x;
-            self.poke_register(Register::RegX, new_bytes_x);
+    // CHAR: convert the magnitude of rA into ten decimal digits in character code, and
+    // place them in rA and rX. The signs of rA and rX are unchanged.
+    fn execute_char(&mut self) -> Result<(), MixMachineErr> {
+        let a = self.register(Register::RegA);
+        let x = self.register(Register::RegX);
+        let mut value = B::magnitude(a);
+        let mut characters = [0u64; 2];     // The bytes for rX, then rA
+        for i in 0..10 {
+            characters[i / 5] += (DIGIT_ZERO as u64 + value % 10) * B::power((i % 5) as u32);
+            value /= 10;
         }
-        new_bytes_a = new_bytes_a | sign_a;
-        self.poke_register(Register::RegA, new_bytes_a);
+        self.set_register(Register::RegA, B::sign(a) | characters[1])?;
+        self.set_register(Register::RegX, B::sign(x) | characters[0])
+    }
+
+    // Enter control state at the given location, saving the registers of the interrupted
+    // program in locations -9, ..., -1 in the order rA, rI1, ..., rI6, rX. Location -1
+    // gets the location of the next instruction in (1:2), 8*OV + CI in (3:3), where CI is
+    // 0, 1, 2 for less, equal, greater, and the contents of rJ in (4:5).
+    fn enter_interrupt(&mut self, location: i16) -> Result<(), MixMachineErr> {
+        let registers = [Register::RegA, Register::RegI1, Register::RegI2, Register::RegI3,
+                         Register::RegI4, Register::RegI5, Register::RegI6, Register::RegX];
+        for (i, reg) in registers.iter().enumerate() {
+            let value = self.register(*reg);
+            self.set_control_memory_word(SAVED_STATE_LOCATION - 8 + i as i16, value)?;
+        }
+        let comparison = match self.comparison_indicator {
+            ComparisonState::Less => 0u64,
+            ComparisonState::Equal => 1u64,
+            ComparisonState::Greater => 2u64,
+        };
+        let overflow = if self.overflow_toggle_on { 8u64 } else { 0u64 };
+        let state = self.program_counter as u64 * B::power(3) + (overflow + comparison) * B::power(2)
+                  + B::magnitude(self.register_J);
+        self.set_control_memory_word(SAVED_STATE_LOCATION, state)?;
+        self.control_state = true;
+        self.program_counter = location;
         Ok(())
     }
 
-    pub fn execute_move_op(&mut self, op : &MoveOp) -> Result<(), MixMachineErr> {
-        let source_start = self.compute_effective_address(op.address, op.index_spec)?;
-        let dest_start = self.peek_register(Register::RegI1)?;
-        if ((source_start as u16) + op.num_to_move) >= MEM_SIZE || ((dest_start as u16) + op.num_to_move) >= MEM_SIZE {
-            Err(MixMachineErr { message : String::from("Attempting to move from invalid address.") })
+    // Return to normal state, restoring the registers saved by enter_interrupt
+    fn leave_interrupt(&mut self) -> Result<(), MixMachineErr> {
+        let registers = [Register::RegA, Register::RegI1, Register::RegI2, Register::RegI3,
+                         Register::RegI4, Register::RegI5, Register::RegI6, Register::RegX];
+        for (i, reg) in registers.iter().enumerate() {
+            let value = self.control_memory_word(SAVED_STATE_LOCATION - 8 + i as i16)?;
+            let value = match *reg {
+                Register::RegA | Register::RegX => value,
+                _ => B::sign(value) | (B::magnitude(value) % B::power(2)),   // Index registers take only the sign and bytes 4:5
+            };
+            self.set_register(*reg, value)?;
+        }
+        let state = self.control_memory_word(SAVED_STATE_LOCATION)?;
+        self.program_counter = (B::magnitude(state) / B::power(3)) as i16;
+        self.overflow_toggle_on = B::byte(state, 3) >= 8;
+        self.comparison_indicator = match B::byte(state, 3) % 8 {
+            0 => ComparisonState::Less,
+            1 => ComparisonState::Equal,
+            _ => ComparisonState::Greater,
+        };
+        self.register_J = B::magnitude(state) % B::power(2);
+        self.control_state = false;
+        Ok(())
+    }
+
+    // INT: in normal state, cause an interrupt to location -12. In control state,
+    // return to the interrupted program.
+    fn execute_interrupt(&mut self) -> Result<(), MixMachineErr> {
+        if self.control_state {
+            self.leave_interrupt()
         } else {
-            for i in 0..op.num_to_move {
-                let x = self.peek_memory(source_start + i).unwrap();
-                self.poke_memory((dest_start as u16) + i, x)?;
-            }
-            self.poke_register(Register::RegI1, dest_start + (op.num_to_move as u32));
+            self.enter_interrupt(INT_INTERRUPT_LOCATION)
+        }
+    }
+
+    // Queue an interrupt, which will happen as soon as the machine is in normal state
+    pub fn request_interrupt(&mut self, location: i16) -> Result<(), MixMachineErr> {
+        if !self.interrupts_enabled {
+            Err(MixMachineErr { message : String::from("Interrupts are not enabled on this machine") })
+        } else if location >= 0 || location < -(CONTROL_MEM_SIZE as i16) {
+            Err(MixMachineErr{message: format!("Invalid interrupt location {}.", location)})
+        } else {
+            self.pending_interrupts.push_back(location);
             Ok(())
         }
     }
 
+    // The interval timer in location -10 counts down by the time each instruction takes,
+    // in units of u, while it is positive. It causes an interrupt to location -11 when it
+    // reaches zero.
+    fn tick_interval_timer(&mut self, time: u32) -> Result<(), MixMachineErr> {
+        if !self.interrupts_enabled {
+            return Ok(());
+        }
+        let timer = B::to_signed(self.control_memory_word(INTERVAL_TIMER_LOCATION)?);
+        if timer > 0 {
+            let remaining = (timer - time as i64).max(0);
+            self.set_control_memory_word(INTERVAL_TIMER_LOCATION, remaining as u64)?;
+            if remaining == 0 {
+                self.request_interrupt(TIMER_INTERRUPT_LOCATION)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Why this machine cannot execute the instruction, if it cannot. Floating point,
+    // interrupts and the binary extensions may be turned off, the binary extensions need
+    // a binary MIX, and I/O needs a device on the unit given by the field.
+    fn unavailable(&self, def: &InstructionDef, field_spec: u8) -> Option<TrapKind> {
+        match def.class {
+            OperandClass::Float(_) if !self.floating_point_enabled => Some(TrapKind::Unavailable),
+            OperandClass::Interrupt if !self.interrupts_enabled => Some(TrapKind::Unavailable),
+            OperandClass::InputOutput(_) if !self.has_device(field_spec) => Some(TrapKind::NoDevice(field_spec)),
+            _ if def.is_binary_extension() && !(B::BINARY && self.binary_extensions_enabled) => Some(TrapKind::Unavailable),
+            _ => None,
+        }
+    }
+
+    // Act on an illegal instruction according to the trap policy. Ok means skip it.
+    fn trap(&mut self, trap: Trap<B::Word>) -> Result<(), MixMachineErr> {
+        let action = match self.trap_policy {
+            TrapPolicy::Stop => TrapAction::Stop,
+            TrapPolicy::Skip => TrapAction::Skip,
+            TrapPolicy::Handler(ref mut handler) => handler(&trap),
+        };
+        match action {
+            TrapAction::Stop => {
+                // Show the instruction as MIXAL where it has a mnemonic
+                let instruction = Operation::disassemble::<B>(trap.word).unwrap_or_else(|_| format!("{:?}", trap.word));
+                Err(MixMachineErr{message: format!("Illegal instruction {} at location {}: {}.", instruction, trap.location, trap.kind.description())})
+            },
+            TrapAction::Skip => Ok(()),
+        }
+    }
+
     pub fn step(&mut self) -> Result<(), MixMachineErr> {
+        if self.halted {
+            return Err(MixMachineErr{message: String::from("Attempted to step a halted machine.")});
+        }
+        // Interrupts are only taken in normal state
+        if !self.control_state {
+            if let Some(location) = self.pending_interrupts.pop_front() {
+                self.enter_interrupt(location)?;
+            }
+        }
         // Try instruction fetch
+        self.instruction_location = self.program_counter;
+        self.io_wait = 0;
         let instruction =
-            if self.program_counter < MEM_SIZE {
-                Ok(self.memory[self.program_counter as usize])
+            if (self.program_counter as i32) < self.memory_size() as i32 && (self.program_counter >= 0 || self.control_state) {
+                self.read_memory(self.program_counter)
             } else {
                 Err(MixMachineErr{message: format!("Attempted instruction fetch from invalid memory address {}.", self.program_counter)})
             };
-        self.program_counter = self.program_counter + 1;    // Need to increment now, since we may modify this in a jump op
-        instruction.and_then(|instruction| {
-            let op = Operation::from_u32(instruction);
-            op.or_else(|_| Err(MixMachineErr{message: format!("Unknown or unimplemeted instruction: {}", instruction)}))
-        }).and_then(|op| {
-            match op {
+        let location = self.program_counter;
+        self.program_counter += 1;    // Need to increment now, since we may modify this in a jump op
+        let result = instruction.and_then(|instruction| {
+            let (negative_address, address, index_spec, field_spec, op_code) = B::split_instruction(instruction);
+            let def = match InstructionDef::lookup(op_code, field_spec) {
+                Some(def) if !def.is_valid_field(field_spec) => Err(TrapKind::InvalidField),
+                Some(_) if index_spec > 6 => Err(TrapKind::InvalidIndex),
+                Some(def) => self.unavailable(def, field_spec).map_or(Ok(def), Err),
+                None if InstructionDef::is_op_code(op_code) => Err(TrapKind::InvalidField),
+                None => Err(TrapKind::IllegalOpCode),
+            };
+            match def {
+                Ok(def) => Ok(Some((def.decode(negative_address, address, index_spec, field_spec), def.execution_time(field_spec)))),
+                Err(kind) => self.trap(Trap {kind, location, word: B::to_word(instruction)}).map(|_| None),
+            }
+        }).and_then(|decoded| {
+            let (op, mut time) = match decoded {
+                Some(decoded) => decoded,
+                None => return Ok(0),   // A skipped instruction takes no time
+            };
+            let result = match op {
                 Load(op)  => self.execute_load_op(&op),
                 Store(op) => self.execute_store_op(&op),
                 Arithmetic(op) => self.execute_arithmetic_op(&op),
+                FloatingPoint(op) => self.execute_float_op(&op),
                 AddressTransfer(op) => self.execute_address_transfer(&op),
                 Comparison(op) => self.execute_comparison_op(&op),
                 Jump(op) => self.execute_jump_op(&op),
                 Shift(op) => self.execute_shift_op(&op),
                 Move(op) => self.execute_move_op(&op),
+                InputOutput(op) => self.execute_io_op(&op).map(|wait| time += wait),
                 NoOp => Ok(()),
-                _         => panic!("Not implemented."),
-            }
+                Halt => { self.halted = true; Ok(()) },
+                Num => self.execute_num(),
+                Char => self.execute_char(),
+                Interrupt => self.execute_interrupt(),
+            };
+            result.map(|_| time)
+        });
+        result.and_then(|time| {
+            self.last_step_time = time;
+            self.elapsed_time += time as u64;
+            self.tick_interval_timer(time)
         })
     }
+
+    // Execute instructions until the machine halts, or until an instruction fails
+    pub fn run(&mut self) -> Result<(), MixMachineErr> {
+        loop {
+            self.step()?;
+            if self.halted { return Ok(()) }
+        }
+    }
+}
+
+impl<B: ByteSize> MixMachineBuilder<B> {
+    // At most b^2 words, since that is the largest address an instruction can hold
+    pub fn memory_size(mut self, memory_size: u16) -> MixMachineBuilder<B> {
+        self.memory_size = memory_size;
+        self
+    }
+
+    pub fn register(mut self, reg: Register, value: B::Word) -> MixMachineBuilder<B> {
+        self.registers.push((reg, value));
+        self
+    }
+
+    // Location of the first instruction to be executed
+    pub fn program_counter(mut self, program_counter: u16) -> MixMachineBuilder<B> {
+        self.program_counter = program_counter;
+        self
+    }
+
+    pub fn floating_point(mut self, enabled: bool) -> MixMachineBuilder<B> {
+        self.floating_point = enabled;
+        self
+    }
+
+    pub fn interrupts(mut self, enabled: bool) -> MixMachineBuilder<B> {
+        self.interrupts = enabled;
+        self
+    }
+
+    // SLB, SRB, JxE and JxO. Only available on a binary machine.
+    pub fn binary_extensions(mut self, enabled: bool) -> MixMachineBuilder<B> {
+        self.binary_extensions = enabled;
+        self
+    }
+
+    // What to do on an illegal instruction. The machine stops by default.
+    pub fn trap_policy(mut self, policy: TrapPolicy<B::Word>) -> MixMachineBuilder<B> {
+        self.trap_policy = policy;
+        self
+    }
+
+    // Report an error when a program touches the block of an IN or OUT before the unit
+    // has finished with it. Off by default.
+    pub fn io_conflict_checks(mut self, enabled: bool) -> MixMachineBuilder<B> {
+        self.io_conflict_checks = enabled;
+        self
+    }
+
+    pub fn build(self) -> Result<GenericMixMachine<B>, MixMachineErr> {
+        if self.memory_size == 0 || self.memory_size as u64 > B::power(2) {
+            return Err(MixMachineErr{message: format!("Invalid memory size {}. Must be between 1 and {}.", self.memory_size, B::power(2))});
+        }
+        if self.program_counter >= self.memory_size {
+            return Err(MixMachineErr{message: format!("Initial program counter {} is outside memory.", self.program_counter)});
+        }
+        if self.binary_extensions && !B::BINARY {
+            return Err(MixMachineErr{message: String::from("Binary extensions are only available on a binary MIX.")});
+        }
+        let mut mix_machine = GenericMixMachine::new();
+        mix_machine.memory = vec![0; self.memory_size as usize];
+        mix_machine.program_counter = self.program_counter as i16;
+        mix_machine.floating_point_enabled = self.floating_point;
+        mix_machine.interrupts_enabled = self.interrupts;
+        mix_machine.binary_extensions_enabled = self.binary_extensions;
+        mix_machine.io_conflict_checks_enabled = self.io_conflict_checks;
+        mix_machine.trap_policy = self.trap_policy;
+        for (reg, value) in self.registers {
+            mix_machine.poke_register(reg, value)?;
+        }
+        Ok(mix_machine)
+    }
 }
 
+#[cfg(test)]
+#[allow(clippy::erasing_op, clippy::double_parens, clippy::identity_op, clippy::neg_multiply, unused_parens)]
 mod tests {
     use super::*;
 
-    fn make_word(positive: bool, b1: u8, b2: u8, b3: u8, b4: u8, b5: u8) -> u32 {
+    fn make_word(positive: bool, b1: u8, b2: u8, b3: u8, b4: u8, b5: u8) -> u64 {
         let max_byte = (1<<6) - 1;
         if    b1 > max_byte || b2 > max_byte || b3 > max_byte
            || b4 > max_byte || b5 > max_byte {
             panic!("Invalid bytes.")
         } else {
-            let sgn = if positive { 0u32 } else { (1u32<<30) };
-            sgn + ((b1 as u32)<<(6*4)) + ((b2 as u32)<<(6*3))
-                + ((b3 as u32)<<(6*2)) + ((b4 as u32)<<(6*1)) + (b5 as u32)
+            let sgn = if positive { 0u64 } else { (1u64<<30) };
+            sgn + ((b1 as u64)<<(6*4)) + ((b2 as u64)<<(6*3))
+                + ((b3 as u64)<<(6*2)) + ((b4 as u64)<<(6*1)) + (b5 as u64)
         }
     }
 
     #[test]
     fn test_negate_value() {
         // 31 ones -> zero then 30 ones
-        assert_eq!(MixMachine::negate_value((1u32<<31)-1), ((1u32<<30)-1));
+        assert_eq!(MixMachine::negate_value((1u64<<31)-1), ((1u64<<30)-1));
         // zero then 30 ones -> 31 ones
-        assert_eq!(MixMachine::negate_value((1u32<<30)-1), ((1u32<<31)-1));
+        assert_eq!(MixMachine::negate_value((1u64<<30)-1), ((1u64<<31)-1));
     }
 
     #[test]
@@ -652,18 +1286,27 @@ mod tests {
     }
 
     #[test]
-    fn test_reg32_to_reg16() {
-        assert_eq!(MixMachine::reg32_to_reg16(make_word(true,  0u8, 0u8, 0u8, 2u8, 5u8)), Ok((5u16 + (2u16 << 6) + (0u16 << 12))));
-        assert_eq!(MixMachine::reg32_to_reg16(make_word(false, 0u8, 0u8, 0u8, 2u8, 5u8)), Ok((5u16 + (2u16 << 6) + (1u16 << 12))));
+    fn test_embed_from_field_decimal() {
+        assert_eq!(Ok(Decimal::from_word(Decimal::make_word(true, [99, 4, 5, 98, 97]))),
+                   DecimalMixMachine::embed_from_field(Decimal::from_word(Decimal::make_word(false, [1, 2, 3, 4, 5])),
+                                                       Decimal::from_word(Decimal::make_word(true, [99, 99, 98, 98, 97])),
+                                                       8*2 + 3));
+    }
+
+    #[test]
+    fn test_two_byte_registers() {
+        let mut mix_machine = MixMachine::new();
+        assert_eq!(mix_machine.set_register(Register::RegI1, make_word(false, 0u8, 0u8, 0u8, 2u8, 5u8)), Ok(()));
+        assert_eq!(mix_machine.register(Register::RegI1), make_word(false, 0u8, 0u8, 0u8, 2u8, 5u8));
+        assert!(mix_machine.set_register(Register::RegI1, make_word(true, 0u8, 0u8, 1u8, 2u8, 5u8)).is_err());
     }
-        
 
     #[test]
-    fn test_reg32_to_i32() {
-        assert_eq!(MixMachine::reg32_to_i32(make_word(true, 0u8, 0u8, 0u8, 0u8, 1u8)), 1i32);
-        assert_eq!(MixMachine::reg32_to_i32(make_word(true, 1u8, 0u8, 0u8, 0u8, 10u8)), ((1i32 << 24) + 10i32));
-        assert_eq!(MixMachine::reg32_to_i32(make_word(false, 0u8, 0u8, 0u8, 0u8, 1u8)), -1i32);
-        assert_eq!(MixMachine::reg32_to_i32(make_word(false, 1u8, 0u8, 0u8, 0u8, 10u8)), -1i32*((1i32 << 24) + 10i32));
+    fn test_word_to_signed() {
+        assert_eq!(Binary::to_signed(make_word(true, 0u8, 0u8, 0u8, 0u8, 1u8)), 1i64);
+        assert_eq!(Binary::to_signed(make_word(true, 1u8, 0u8, 0u8, 0u8, 10u8)), ((1i64 << 24) + 10i64));
+        assert_eq!(Binary::to_signed(make_word(false, 0u8, 0u8, 0u8, 0u8, 1u8)), -1i64);
+        assert_eq!(Binary::to_signed(make_word(false, 1u8, 0u8, 0u8, 0u8, 10u8)), -1i64*((1i64 << 24) + 10i64));
     }
 
     #[test]
@@ -672,5 +1315,6 @@ mod tests {
         assert_eq!(MixMachine::i32_to_reg32(10i32), 10u32);
         assert_eq!(MixMachine::i32_to_reg32((1i32 << 30) - 1i32), (1u32 << 30) - 1u32);
         assert_eq!(MixMachine::i32_to_reg32(-1i32), (1u32 << 30) + 1u32);
+        assert_eq!(DecimalMixMachine::i32_to_reg32(-1i32), (1u64 << 34) + 1u64);
     }
 }
@@ -0,0 +1,139 @@
+// A scriptable device held entirely in memory, for testing programs that do I/O.
+//
+// Input is given in advance as blocks of words or as lines of text, and output is kept
+// as a list of blocks. Every IN, OUT and IOC is recorded, in order. A MockDevice and
+// its clones share the same state, so a test can attach one clone to a machine and use
+// another to script the input and check what happened.
+
+use mix_char::{text_to_words, words_to_printable};
+use mix_device::Device;
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceCall<W> {
+    In { rx: W },
+    Out { block: Vec<W>, rx: W },
+    Control { m: i16, rx: W },
+}
+
+struct MockState<B: ByteSize> {
+    block_size: usize,
+    input: VecDeque<Vec<B::Word>>,
+    output: Vec<Vec<B::Word>>,
+    calls: Vec<DeviceCall<B::Word>>,
+    busy: bool,
+    latency: u32,
+}
+
+pub struct MockDevice<B: ByteSize> {
+    state: Rc<RefCell<MockState<B>>>,
+}
+
+impl<B: ByteSize> Clone for MockDevice<B> {
+    fn clone(&self) -> MockDevice<B> {
+        MockDevice{state: self.state.clone()}
+    }
+}
+
+impl<B: ByteSize> MockDevice<B> {
+    pub fn new(block_size: usize) -> MockDevice<B> {
+        MockDevice{state: Rc::new(RefCell::new(MockState{
+            block_size,
+            input: VecDeque::new(),
+            output: Vec::new(),
+            calls: Vec::new(),
+            busy: false,
+            latency: 0,
+        }))}
+    }
+
+    // Add a block to be read by a later IN. Short blocks are padded with +0.
+    pub fn push_input(&self, block: &[B::Word]) -> Result<(), MixMachineErr> {
+        let mut state = self.state.borrow_mut();
+        if block.len() > state.block_size {
+            return Err(MixMachineErr{message: format!("Block of {} words is longer than {}.", block.len(), state.block_size)});
+        }
+        let mut block = block.to_vec();
+        block.resize(state.block_size, B::to_word(0u64));
+        state.input.push_back(block);
+        Ok(())
+    }
+
+    // Add a line of text to be read by a later IN, in the MIX character code
+    pub fn push_line(&self, line: &str) -> Result<(), MixMachineErr> {
+        let block_size = self.state.borrow().block_size;
+        let block = text_to_words::<B>(line, block_size)?;
+        self.push_input(&block)
+    }
+
+    // Number of input blocks not yet read
+    pub fn input_remaining(&self) -> usize {
+        self.state.borrow().input.len()
+    }
+
+    // The blocks written by OUT, in order
+    pub fn output(&self) -> Vec<Vec<B::Word>> {
+        self.state.borrow().output.clone()
+    }
+
+    // The blocks written by OUT as text, as a line printer would show them
+    pub fn output_lines(&self) -> Vec<String> {
+        self.state.borrow().output.iter().map(|block| words_to_printable::<B>(block)).collect()
+    }
+
+    pub fn calls(&self) -> Vec<DeviceCall<B::Word>> {
+        self.state.borrow().calls.clone()
+    }
+
+    // Whether the device reports itself busy to JBUS and JRED
+    pub fn set_busy(&self, busy: bool) {
+        self.state.borrow_mut().busy = busy;
+    }
+
+    // The latency taken by the machine when the device is attached
+    pub fn set_latency(&self, latency: u32) {
+        self.state.borrow_mut().latency = latency;
+    }
+}
+
+impl<B: ByteSize> Device<B> for MockDevice<B> {
+    fn block_size(&self) -> usize {
+        self.state.borrow().block_size
+    }
+
+    fn read(&mut self, block: &mut [B::Word], rx: B::Word) -> Result<(), MixMachineErr> {
+        let mut state = self.state.borrow_mut();
+        state.calls.push(DeviceCall::In{rx});
+        match state.input.pop_front() {
+            Some(input) => {
+                block.copy_from_slice(&input);
+                Ok(())
+            },
+            None => Err(MixMachineErr{message: String::from("No more input for the mock device.")}),
+        }
+    }
+
+    fn write(&mut self, block: &[B::Word], rx: B::Word) -> Result<(), MixMachineErr> {
+        let mut state = self.state.borrow_mut();
+        state.calls.push(DeviceCall::Out{block: block.to_vec(), rx});
+        state.output.push(block.to_vec());
+        Ok(())
+    }
+
+    fn control(&mut self, m: i16, rx: B::Word) -> Result<(), MixMachineErr> {
+        self.state.borrow_mut().calls.push(DeviceCall::Control{m, rx});
+        Ok(())
+    }
+
+    fn is_busy(&self) -> bool {
+        self.state.borrow().busy
+    }
+
+    fn latency(&self) -> u32 {
+        self.state.borrow().latency
+    }
+}
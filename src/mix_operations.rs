@@ -2,18 +2,24 @@ pub enum Operation {
     Load(LoadOp),
     Store(StoreOp),
     Arithmetic(ArithOp),
+    FloatingPoint(FloatOp),
     AddressTransfer(AddressOp),
     Comparison(CompOp),
     Jump(JumpOp),
     Shift(ShiftOp),
     Move(MoveOp),
+    InputOutput(IoOp),
     NoOp,
-    Unknown,
+    Halt,
+    Num,
+    Char,
+    Interrupt,
 }
 
 use mix_operations::Operation::*;
 use mix_machine::Register;
-use mix_machine::Register::*;
+use mix_word::{ByteSize, Binary};
+use mix_instruction_set::{InstructionDef, OperandClass, FieldKind};
 
 pub struct LoadOp {
     pub register: Register,
@@ -30,6 +36,7 @@ pub struct StoreOp {
     pub index_spec: u8,
 }
 
+#[derive(Clone, Copy)]
 pub enum ArithOpType {
     Addition, Subtraction, Multiplication, Division,
 }
@@ -41,6 +48,17 @@ pub struct ArithOp {
     pub index_spec: u8,
 }
 
+#[derive(Clone, Copy)]
+pub enum FloatOpType {
+    Addition, Subtraction, Multiplication, Division, Flot, Fix, Comparison,
+}
+
+pub struct FloatOp {
+    pub op_type: FloatOpType,
+    pub address: i16,
+    pub index_spec: u8,
+}
+
 pub struct AddressOp {
     pub register: Register,
     pub address: i16,
@@ -70,6 +88,7 @@ pub struct ShiftOp {
     pub index_spec : u8,
     pub shift_left : bool,  // If false, shift right
     pub circulating_shift : bool,
+    pub binary : bool,  // SLB and SRB shift rAX by bits rather than bytes
 }
 
 pub struct MoveOp {
@@ -78,92 +97,95 @@ pub struct MoveOp {
     pub num_to_move : u16,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum IoOpType {
+    Input, Output, Control, JumpReady, JumpBusy,
+}
+
+pub struct IoOp {
+    pub op_type: IoOpType,
+    pub unit: u8,   // Given by the field of the instruction
+    pub address: i16,
+    pub index_spec: u8,
+}
+
+#[allow(clippy::result_unit_err)]
 impl Operation {
     pub fn from_u32(instruction: u32) -> Result<Operation, ()> {
-        let op_code: u8    = ( instruction        % 64u32) as u8;
-        let field_spec: u8 = ((instruction >> 6 ) % 64u32) as u8;
-        let index_spec: u8 = ((instruction >> 12) % 64u32) as u8;
-        let negative_address: bool = instruction & (1u32 << 30) != 0;   // Need to distinguish between +0 and -0 in some cases
-        let address: i16   = ((instruction >> 18) % 4096u32) as i16 * (if negative_address { -1i16 } else { 1i16 });
-        
-        match op_code {
-            // Load instructions
-            8  => Ok(Load(LoadOp {register: RegA,  field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            15 => Ok(Load(LoadOp {register: RegX,  field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            9  => Ok(Load(LoadOp {register: RegI1, field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            10 => Ok(Load(LoadOp {register: RegI2, field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            11 => Ok(Load(LoadOp {register: RegI3, field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            12 => Ok(Load(LoadOp {register: RegI4, field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            13 => Ok(Load(LoadOp {register: RegI5, field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            14 => Ok(Load(LoadOp {register: RegI6, field: field_spec, negative: false, address: address, index_spec: index_spec})),
-            // Load negative instructions
-            16 => Ok(Load(LoadOp {register: RegA,  field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            23 => Ok(Load(LoadOp {register: RegX,  field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            17 => Ok(Load(LoadOp {register: RegI1, field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            18 => Ok(Load(LoadOp {register: RegI2, field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            19 => Ok(Load(LoadOp {register: RegI3, field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            20 => Ok(Load(LoadOp {register: RegI4, field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            21 => Ok(Load(LoadOp {register: RegI5, field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            22 => Ok(Load(LoadOp {register: RegI6, field: field_spec, negative: true, address: address, index_spec: index_spec})),
-            // Store instructions
-            24 => Ok(Store(StoreOp {register: Some(RegA),  field: field_spec, address: address, index_spec: index_spec})),
-            31 => Ok(Store(StoreOp {register: Some(RegX),  field: field_spec, address: address, index_spec: index_spec})),
-            25 => Ok(Store(StoreOp {register: Some(RegI1), field: field_spec, address: address, index_spec: index_spec})),
-            26 => Ok(Store(StoreOp {register: Some(RegI2), field: field_spec, address: address, index_spec: index_spec})),
-            27 => Ok(Store(StoreOp {register: Some(RegI3), field: field_spec, address: address, index_spec: index_spec})),
-            28 => Ok(Store(StoreOp {register: Some(RegI4), field: field_spec, address: address, index_spec: index_spec})),
-            29 => Ok(Store(StoreOp {register: Some(RegI5), field: field_spec, address: address, index_spec: index_spec})),
-            30 => Ok(Store(StoreOp {register: Some(RegI6), field: field_spec, address: address, index_spec: index_spec})),
-            32 => Ok(Store(StoreOp {register: Some(RegJ),  field: field_spec, address: address, index_spec: index_spec})),
-            33 => Ok(Store(StoreOp {register: None,        field: field_spec, address: address, index_spec: index_spec})),    // STZ, stores zero
-            // Arithmetic instructions
-            1  => Ok(Arithmetic(ArithOp {op_type: ArithOpType::Addition,       field: field_spec, address: address, index_spec: index_spec })),
-            2  => Ok(Arithmetic(ArithOp {op_type: ArithOpType::Subtraction,    field: field_spec, address: address, index_spec: index_spec })),
-            3  => Ok(Arithmetic(ArithOp {op_type: ArithOpType::Multiplication, field: field_spec, address: address, index_spec: index_spec })),
-            4  => Ok(Arithmetic(ArithOp {op_type: ArithOpType::Division,       field: field_spec, address: address, index_spec: index_spec })),
-            // Address transfer instructions
-            48 => Ok(AddressTransfer(AddressOp {register: RegA,  address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            49 => Ok(AddressTransfer(AddressOp {register: RegI1, address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            50 => Ok(AddressTransfer(AddressOp {register: RegI2, address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            51 => Ok(AddressTransfer(AddressOp {register: RegI3, address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            52 => Ok(AddressTransfer(AddressOp {register: RegI4, address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            53 => Ok(AddressTransfer(AddressOp {register: RegI5, address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            54 => Ok(AddressTransfer(AddressOp {register: RegI6, address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            55 => Ok(AddressTransfer(AddressOp {register: RegX,  address: address, negative_address: negative_address, index_spec: index_spec,  negate_value: field_spec % 2u8 != 0u8, increase: field_spec / 2u8 == 0u8})),
-            // Comparison instructions
-            56 => Ok(Comparison(CompOp {register : RegA,  field : field_spec, address: address, index_spec: index_spec})),
-            57 => Ok(Comparison(CompOp {register : RegI1, field : field_spec, address: address, index_spec: index_spec})),
-            58 => Ok(Comparison(CompOp {register : RegI2, field : field_spec, address: address, index_spec: index_spec})),
-            59 => Ok(Comparison(CompOp {register : RegI3, field : field_spec, address: address, index_spec: index_spec})),
-            60 => Ok(Comparison(CompOp {register : RegI4, field : field_spec, address: address, index_spec: index_spec})),
-            61 => Ok(Comparison(CompOp {register : RegI5, field : field_spec, address: address, index_spec: index_spec})),
-            61 => Ok(Comparison(CompOp {register : RegI6, field : field_spec, address: address, index_spec: index_spec})),
-            63 => Ok(Comparison(CompOp {register : RegX,  field : field_spec, address: address, index_spec: index_spec})),
-            // Jump instructions
-            39 => Ok(Jump(JumpOp {register : None, address : address, index_spec : index_spec, field : field_spec})),
-            40 => Ok(Jump(JumpOp {register : Some(Register::RegA), address : address, index_spec : index_spec, field : field_spec})),
-            47 => Ok(Jump(JumpOp {register : Some(Register::RegX), address : address, index_spec : index_spec, field : field_spec})),
-            41 => Ok(Jump(JumpOp {register : Some(Register::RegI1), address : address, index_spec : index_spec, field : field_spec})),
-            42 => Ok(Jump(JumpOp {register : Some(Register::RegI2), address : address, index_spec : index_spec, field : field_spec})),
-            43 => Ok(Jump(JumpOp {register : Some(Register::RegI3), address : address, index_spec : index_spec, field : field_spec})),
-            44 => Ok(Jump(JumpOp {register : Some(Register::RegI4), address : address, index_spec : index_spec, field : field_spec})),
-            45 => Ok(Jump(JumpOp {register : Some(Register::RegI5), address : address, index_spec : index_spec, field : field_spec})),
-            46 => Ok(Jump(JumpOp {register : Some(Register::RegI6), address : address, index_spec : index_spec, field : field_spec})),
-            // Shift instructions
-            6 => Ok(Shift(ShiftOp {use_reg_x : field_spec > 1, address : address, index_spec : index_spec, shift_left : field_spec % 2 == 0, circulating_shift : field_spec > 3})),
-            7 => Ok(Move(MoveOp {address : address, index_spec : index_spec, num_to_move : field_spec as u16})),
-            0 => Ok(NoOp),
-            // Unknown (or not implemented)
-            _  => Err(())
+        let (negative_address, address, index_spec, field_spec, op_code) = Binary::split_instruction(instruction as u64);
+        Operation::from_fields(negative_address, address, index_spec, field_spec, op_code)
+    }
+
+    // Decode an instruction that has already been split into its parts, so that the
+    // same decoding serves machines of any byte size
+    pub fn from_fields(negative_address: bool, address: u16, index_spec: u8, field_spec: u8, op_code: u8) -> Result<Operation, ()> {
+        let def = InstructionDef::lookup(op_code, field_spec).ok_or(())?;
+        Ok(def.decode(negative_address, address, index_spec, field_spec))
+    }
+
+    // Encode the instruction with the given mnemonic. The field defaults to that given by
+    // Knuth, and may only be given for a fixed field instruction if it matches.
+    pub fn encode<B: ByteSize>(mnemonic: &str, positive: bool, address: u16, index_spec: u8, field_spec: Option<u8>) -> Result<B::Word, ()> {
+        let def = InstructionDef::from_mnemonic(mnemonic).ok_or(())?;
+        let field_spec = match field_spec {
+            Some(f) if def.field_is_fixed && f != def.default_field => return Err(()),
+            Some(f) => f,
+            None => def.default_field,
+        };
+        if address as u64 >= B::power(2) || index_spec as u64 >= B::BYTE_SIZE || field_spec as u64 >= B::BYTE_SIZE {
+            return Err(());
+        }
+        Ok(B::make_instruction(positive, address, index_spec, field_spec, def.op_code))
+    }
+
+    // Write an instruction in MIXAL, e.g. "LDA -2000,3(0:3)". The index and field are
+    // left out when they are zero or the default.
+    pub fn disassemble<B: ByteSize>(instruction: B::Word) -> Result<String, ()> {
+        let (negative_address, address, index_spec, field_spec, op_code) = B::split_instruction(B::from_word(instruction));
+        let def = InstructionDef::lookup(op_code, field_spec).ok_or(())?;
+        let mut text = format!("{} {}{}", def.mnemonic, if negative_address { "-" } else { "" }, address);
+        if index_spec != 0 {
+            text.push_str(&format!(",{}", index_spec));
         }
+        if field_spec != def.default_field {
+            match def.field_kind() {
+                FieldKind::Partial => text.push_str(&format!("({}:{})", field_spec / 8, field_spec % 8)),
+                FieldKind::Number => text.push_str(&format!("({})", field_spec)),
+                FieldKind::Fixed => (),
+            }
+        }
+        Ok(text)
     }
 
+    // An instruction for the binary MIX
     pub fn make_instruction(positive: bool, address: u16, index_spec: u8, field_spec: u8, op_code: u8) -> u32 {
-        if address >= (1u16 << 12) { panic!("Invalid address.") }
-        if index_spec >= (1u8 << 6) { panic!("Invalid index specification.") }
-        if field_spec >= (1u8 << 6) { panic!("Invalid field specification.") }
-        if op_code >= (1u8 << 6) { panic!("Invalid op code.") }
-        let sgn_bit = if positive { 0u32 } else { 1u32 << 30 };
-        sgn_bit + ((address as u32) << 18) + ((index_spec as u32) << 12) + ((field_spec as u32) << 6) + (op_code as u32)
+        Binary::make_instruction(positive, address, index_spec, field_spec, op_code)
+    }
+}
+
+impl InstructionDef {
+    pub fn decode(&self, negative_address: bool, address: u16, index_spec: u8, field_spec: u8) -> Operation {
+        // Need to distinguish between +0 and -0 in some cases
+        let address: i16 = address as i16 * (if negative_address { -1i16 } else { 1i16 });
+
+        match self.class {
+            OperandClass::Load(register, negative) => Load(LoadOp {register, field: field_spec, negative, address, index_spec}),
+            OperandClass::Store(register) => Store(StoreOp {register, field: field_spec, address, index_spec}),
+            OperandClass::Arithmetic(op_type) => Arithmetic(ArithOp {op_type, field: field_spec, address, index_spec}),
+            OperandClass::Float(op_type) => FloatingPoint(FloatOp {op_type, address, index_spec}),
+            OperandClass::AddressTransfer(register) => AddressTransfer(AddressOp {register, address, negative_address, index_spec,
+                                                                                  negate_value: !field_spec.is_multiple_of(2u8), increase: field_spec / 2u8 == 0u8}),
+            OperandClass::Comparison(register) => Comparison(CompOp {register, field: field_spec, address, index_spec}),
+            OperandClass::Jump(register) => Jump(JumpOp {register, address, index_spec, field: field_spec}),
+            OperandClass::Shift => Shift(ShiftOp {use_reg_x: field_spec > 1, address, index_spec, shift_left: field_spec.is_multiple_of(2),
+                                                  circulating_shift: field_spec == 4 || field_spec == 5, binary: field_spec >= 6}),
+            OperandClass::Move => Move(MoveOp {address, index_spec, num_to_move: field_spec as u16}),
+            OperandClass::InputOutput(op_type) => InputOutput(IoOp {op_type, unit: field_spec, address, index_spec}),
+            OperandClass::NoOp => NoOp,
+            OperandClass::Halt => Halt,
+            OperandClass::Num => Num,
+            OperandClass::Char => Char,
+            OperandClass::Interrupt => Interrupt,
+        }
     }
 }
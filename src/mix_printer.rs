@@ -0,0 +1,61 @@
+// The line printer, unit 18.
+//
+// Each OUT prints one line of PRINTER_BLOCK_SIZE words, that is 120 characters in the
+// MIX character code. Trailing blanks are left off, and any byte that is not a MIX
+// character prints as mix_char::UNPRINTABLE. IOC 0 starts a new page by writing the page
+// separator, which is a form feed unless another is chosen.
+
+use mix_char::words_to_printable;
+use mix_device::Device;
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+
+pub const PRINTER_BLOCK_SIZE: usize = 24;
+
+fn io_error(e: io::Error) -> MixMachineErr {
+    MixMachineErr{message: format!("Printer I/O error: {}", e)}
+}
+
+pub struct LinePrinter {
+    output: Box<dyn Write>,
+    page_separator: String,
+}
+
+impl LinePrinter {
+    pub fn new(output: Box<dyn Write>) -> LinePrinter {
+        LinePrinter{output, page_separator: String::from("\u{c}")}
+    }
+
+    // Print to the file at path, replacing anything already there
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<LinePrinter, MixMachineErr> {
+        let file = File::create(path).map_err(io_error)?;
+        Ok(LinePrinter::new(Box::new(file)))
+    }
+
+    // Text written by IOC 0 in place of a form feed, e.g. a line of dashes
+    pub fn page_separator(mut self, page_separator: &str) -> LinePrinter {
+        self.page_separator = String::from(page_separator);
+        self
+    }
+}
+
+impl<B: ByteSize> Device<B> for LinePrinter {
+    fn block_size(&self) -> usize {
+        PRINTER_BLOCK_SIZE
+    }
+
+    fn write(&mut self, block: &[B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        writeln!(self.output, "{}", words_to_printable::<B>(block)).and_then(|_| self.output.flush()).map_err(io_error)
+    }
+
+    fn control(&mut self, m: i16, _rx: B::Word) -> Result<(), MixMachineErr> {
+        if m != 0 {
+            return Err(MixMachineErr{message: format!("Invalid control operation {} for a line printer.", m)});
+        }
+        write!(self.output, "{}", self.page_separator).and_then(|_| self.output.flush()).map_err(io_error)
+    }
+}
@@ -0,0 +1,97 @@
+// Magnetic tape units, normally attached as units 0 to 7.
+//
+// A tape is kept in an ordinary host file, so that it survives between runs. The file
+// holds the blocks one after another, in the format described in mix_device. As on a
+// real tape, writing a block erases everything after it.
+//
+// IOC with M = 0 rewinds the tape. M < 0 skips back -M blocks (stopping at the start
+// of the tape), and M > 0 skips forward M blocks.
+
+use mix_device::{Device, BYTES_PER_WORD, block_to_bytes, block_from_bytes};
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+pub const TAPE_BLOCK_SIZE: usize = 100;
+const BYTES_PER_BLOCK: u64 = (TAPE_BLOCK_SIZE * BYTES_PER_WORD) as u64;
+
+pub struct TapeUnit {
+    file: File,
+    position: u64,  // Number of the block under the read/write head
+}
+
+fn io_error(e: io::Error) -> MixMachineErr {
+    MixMachineErr{message: format!("Tape I/O error: {}", e)}
+}
+
+impl TapeUnit {
+    // Mount the tape held in the file at path, creating an empty tape if there is no
+    // such file. The tape starts out rewound.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<TapeUnit, MixMachineErr> {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path).map_err(io_error)?;
+        Ok(TapeUnit{file, position: 0})
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    // Number of blocks written on the tape
+    pub fn length(&self) -> Result<u64, MixMachineErr> {
+        self.file.metadata().map(|metadata| metadata.len() / BYTES_PER_BLOCK).map_err(io_error)
+    }
+
+    // Block n of the tape, without moving the tape
+    pub fn block<B: ByteSize>(&mut self, n: u64) -> Result<Vec<B::Word>, MixMachineErr> {
+        let mut block = vec![B::to_word(0u64); TAPE_BLOCK_SIZE];
+        self.read_block::<B>(n, &mut block)?;
+        Ok(block)
+    }
+
+    fn read_block<B: ByteSize>(&mut self, n: u64, block: &mut [B::Word]) -> Result<(), MixMachineErr> {
+        if n >= self.length()? {
+            return Err(MixMachineErr{message: String::from("Attempt to read past the end of the tape.")});
+        }
+        let mut bytes = vec![0u8; BYTES_PER_BLOCK as usize];
+        self.file.seek(SeekFrom::Start(n * BYTES_PER_BLOCK)).map_err(io_error)?;
+        self.file.read_exact(&mut bytes).map_err(io_error)?;
+        block_from_bytes::<B>(&bytes, block)
+    }
+}
+
+impl<B: ByteSize> Device<B> for TapeUnit {
+    fn block_size(&self) -> usize {
+        TAPE_BLOCK_SIZE
+    }
+
+    fn read(&mut self, block: &mut [B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        let position = self.position;
+        self.read_block::<B>(position, block)?;
+        self.position = position + 1;
+        Ok(())
+    }
+
+    fn write(&mut self, block: &[B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        let bytes = block_to_bytes::<B>(block);
+        self.file.seek(SeekFrom::Start(self.position * BYTES_PER_BLOCK)).map_err(io_error)?;
+        self.file.write_all(&bytes).map_err(io_error)?;
+        self.position += 1;
+        self.file.set_len(self.position * BYTES_PER_BLOCK).map_err(io_error)
+    }
+
+    fn control(&mut self, m: i16, _rx: B::Word) -> Result<(), MixMachineErr> {
+        if m == 0 {
+            self.position = 0;
+        } else if m < 0 {
+            self.position = self.position.saturating_sub(m.unsigned_abs() as u64);
+        } else if self.position + m as u64 > self.length()? {
+            return Err(MixMachineErr{message: String::from("Attempt to skip past the end of the tape.")});
+        } else {
+            self.position += m as u64;
+        }
+        Ok(())
+    }
+}
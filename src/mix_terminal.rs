@@ -0,0 +1,115 @@
+// The typewriter terminal (unit 19) and paper tape reader (unit 20).
+//
+// Both have blocks of TERMINAL_BLOCK_SIZE words, holding one line of 70 characters in
+// the MIX character code. Input lines are padded with blanks. The typewriter reads
+// lines from any BufRead and types them to any Write, leaving off trailing blanks.
+// Paper tape is read and punched line by line in a host file, and IOC 0 rewinds it. As
+// on a magnetic tape, punching a line erases everything after it.
+
+use mix_char::{text_to_words, words_to_printable};
+use mix_device::Device;
+use mix_machine::MixMachineErr;
+use mix_word::ByteSize;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::Path;
+
+pub const TERMINAL_BLOCK_SIZE: usize = 14;
+
+// An I/O error of the host, reported with the name of the device it happened on
+fn io_error(device: &str, e: io::Error) -> MixMachineErr {
+    MixMachineErr{message: format!("{} I/O error: {}", device, e)}
+}
+
+// Read the next line of input into block, or return false at the end of the input
+fn read_line<B: ByteSize, R: BufRead + ?Sized>(device: &str, input: &mut R, block: &mut [B::Word]) -> Result<bool, MixMachineErr> {
+    let mut line = String::new();
+    if input.read_line(&mut line).map_err(|e| io_error(device, e))? == 0 {
+        return Ok(false);
+    }
+    let words = text_to_words::<B>(line.trim_end_matches(['\n', '\r']), TERMINAL_BLOCK_SIZE)?;
+    block.copy_from_slice(&words);
+    Ok(true)
+}
+
+pub struct Typewriter {
+    input: Box<dyn BufRead>,
+    output: Box<dyn Write>,
+}
+
+pub struct PaperTape {
+    tape: BufReader<File>,
+}
+
+impl Typewriter {
+    pub fn new(input: Box<dyn BufRead>, output: Box<dyn Write>) -> Typewriter {
+        Typewriter{input, output}
+    }
+
+    // A typewriter on the standard input and output of the host
+    pub fn stdio() -> Typewriter {
+        Typewriter::new(Box::new(BufReader::new(io::stdin())), Box::new(io::stdout()))
+    }
+}
+
+impl PaperTape {
+    // Mount the tape held in the file at path, creating a blank tape if there is no such
+    // file. The tape starts out rewound.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<PaperTape, MixMachineErr> {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)
+            .map_err(|e| io_error("Paper tape", e))?;
+        Ok(PaperTape{tape: BufReader::new(file)})
+    }
+}
+
+impl<B: ByteSize> Device<B> for Typewriter {
+    fn block_size(&self) -> usize {
+        TERMINAL_BLOCK_SIZE
+    }
+
+    fn read(&mut self, block: &mut [B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        if read_line::<B, _>("Typewriter", &mut *self.input, block)? {
+            Ok(())
+        } else {
+            Err(MixMachineErr{message: String::from("End of input on the typewriter.")})
+        }
+    }
+
+    fn write(&mut self, block: &[B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        writeln!(self.output, "{}", words_to_printable::<B>(block)).and_then(|_| self.output.flush()).map_err(|e| io_error("Typewriter", e))
+    }
+}
+
+impl<B: ByteSize> Device<B> for PaperTape {
+    fn block_size(&self) -> usize {
+        TERMINAL_BLOCK_SIZE
+    }
+
+    fn read(&mut self, block: &mut [B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        if read_line::<B, _>("Paper tape", &mut self.tape, block)? {
+            Ok(())
+        } else {
+            Err(MixMachineErr{message: String::from("Attempt to read past the end of the paper tape.")})
+        }
+    }
+
+    fn write(&mut self, block: &[B::Word], _rx: B::Word) -> Result<(), MixMachineErr> {
+        // Seeking drops what the reader has buffered, leaving the file at the read position
+        self.tape.stream_position().and_then(|position| self.tape.seek(SeekFrom::Start(position)))
+            .map_err(|e| io_error("Paper tape", e))?;
+        let file = self.tape.get_mut();
+        writeln!(file, "{}", words_to_printable::<B>(block))
+            .and_then(|_| file.stream_position())
+            .and_then(|end| file.set_len(end))
+            .map_err(|e| io_error("Paper tape", e))
+    }
+
+    // Only IOC 0, which rewinds the tape, is defined
+    fn control(&mut self, m: i16, _rx: B::Word) -> Result<(), MixMachineErr> {
+        if m != 0 {
+            return Err(MixMachineErr{message: format!("Invalid control operation {} for paper tape.", m)});
+        }
+        self.tape.seek(SeekFrom::Start(0)).map(|_| ()).map_err(|e| io_error("Paper tape", e))
+    }
+}
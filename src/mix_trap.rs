@@ -0,0 +1,51 @@
+// Traps for instructions that cannot be executed: an op code with no instruction, a
+// field that the instruction does not accept, an index other than 0 to 6, an instruction
+// this machine lacks, such as FADD with floating point turned off or SLB on a decimal
+// MIX, or I/O on a unit with no device.
+//
+// The host decides what happens by choosing a TrapPolicy. By default the machine stops,
+// and step() returns an error.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TrapKind {
+    IllegalOpCode,
+    InvalidField,
+    InvalidIndex,
+    Unavailable,
+    NoDevice(u8),   // The unit given by the field
+}
+
+// The faulting instruction, and the location it was fetched from
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Trap<W> {
+    pub kind: TrapKind,
+    pub location: i16,
+    pub word: W,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TrapAction {
+    Stop,   // step() returns an error
+    Skip,   // Carry on with the next instruction, as though the faulting one were NOP
+}
+
+// Called with each trap, to decide what to do about it
+pub type TrapHandler<W> = Box<dyn FnMut(&Trap<W>) -> TrapAction>;
+
+pub enum TrapPolicy<W> {
+    Stop,
+    Skip,
+    Handler(TrapHandler<W>),
+}
+
+impl TrapKind {
+    pub fn description(&self) -> String {
+        match *self {
+            TrapKind::IllegalOpCode => String::from("illegal op code"),
+            TrapKind::InvalidField => String::from("invalid field specification"),
+            TrapKind::InvalidIndex => String::from("invalid index specification"),
+            TrapKind::Unavailable => String::from("instruction not available on this machine"),
+            TrapKind::NoDevice(unit) => format!("no device attached to unit {}", unit),
+        }
+    }
+}
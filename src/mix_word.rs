@@ -0,0 +1,145 @@
+// Word formats for MIX machines with different byte sizes.
+//
+// Knuth deliberately leaves the size of a MIX byte open: a byte holds at least 64 and
+// at most 100 distinct values, and a well written program works whichever is used. A
+// word is five bytes plus a sign.
+//
+// Inside the emulator every word is held in a u64 as its magnitude (the five bytes read
+// as a number in base BYTE_SIZE) plus the flag SIGN_BIT, which is set for negative
+// words. The public interface of each machine uses the narrower type Word, which has
+// the same layout.
+
+use std::fmt::Debug;
+
+pub trait ByteSize {
+    type Word: Copy + PartialEq + Debug;
+
+    // Number of distinct values a byte can hold
+    const BYTE_SIZE: u64;
+    // Flag marking a negative word. All lower bits are available for the magnitude.
+    const SIGN_BIT: u64;
+    // SLB, SRB, JxE and JxO only exist on a binary machine
+    const BINARY: bool;
+
+    fn from_word(word: Self::Word) -> u64;
+    fn to_word(value: u64) -> Self::Word;
+
+    // BYTE_SIZE^n, so power(5) is the number of distinct word magnitudes
+    fn power(n: u32) -> u64 {
+        Self::BYTE_SIZE.pow(n)
+    }
+
+    fn magnitude(value: u64) -> u64 {
+        value & (Self::SIGN_BIT - 1)
+    }
+
+    fn sign(value: u64) -> u64 {
+        value & Self::SIGN_BIT
+    }
+
+    fn is_valid(value: u64) -> bool {
+        value & !(Self::SIGN_BIT | (Self::SIGN_BIT - 1)) == 0 && Self::magnitude(value) < Self::power(5)
+    }
+
+    fn to_signed(value: u64) -> i64 {
+        if Self::sign(value) == 0 { Self::magnitude(value) as i64 } else { -(Self::magnitude(value) as i64) }
+    }
+
+    // The magnitude of value must be less than power(5)
+    fn from_signed(value: i64) -> u64 {
+        if value >= 0 { value as u64 } else { value.unsigned_abs() | Self::SIGN_BIT }
+    }
+
+    // Byte i (1 to 5) of a word
+    fn byte(value: u64, i: u32) -> u64 {
+        (Self::magnitude(value) / Self::power(5 - i)) % Self::BYTE_SIZE
+    }
+
+    fn make_word(positive: bool, bytes: [u64; 5]) -> Self::Word {
+        let magnitude = bytes.iter().fold(0u64, |acc, &b| {
+            if b >= Self::BYTE_SIZE { panic!("Invalid byte {}.", b) }
+            acc * Self::BYTE_SIZE + b
+        });
+        Self::to_word(if positive { magnitude } else { magnitude | Self::SIGN_BIT })
+    }
+
+    fn make_instruction(positive: bool, address: u16, index_spec: u8, field_spec: u8, op_code: u8) -> Self::Word {
+        if address as u64 >= Self::power(2) { panic!("Invalid address.") }
+        if index_spec as u64 >= Self::BYTE_SIZE { panic!("Invalid index specification.") }
+        if field_spec as u64 >= Self::BYTE_SIZE { panic!("Invalid field specification.") }
+        if op_code as u64 >= Self::BYTE_SIZE { panic!("Invalid op code.") }
+        let magnitude = address as u64 * Self::power(3) + index_spec as u64 * Self::power(2)
+                      + field_spec as u64 * Self::BYTE_SIZE + op_code as u64;
+        Self::to_word(if positive { magnitude } else { magnitude | Self::SIGN_BIT })
+    }
+
+    // Split an instruction into its sign, address, index, field and op code
+    fn split_instruction(value: u64) -> (bool, u16, u8, u8, u8) {
+        (Self::sign(value) != 0, (Self::magnitude(value) / Self::power(3)) as u16,
+         Self::byte(value, 3) as u8, Self::byte(value, 4) as u8, Self::byte(value, 5) as u8)
+    }
+}
+
+// The binary MIX described in the README: 6-bit bytes, with words held in a u32 whose
+// bit 30 is the sign
+pub struct Binary;
+
+impl ByteSize for Binary {
+    type Word = u32;
+    const BYTE_SIZE: u64 = 64;
+    const SIGN_BIT: u64 = 1u64 << 30;
+    const BINARY: bool = true;
+
+    fn from_word(word: u32) -> u64 { word as u64 }
+    fn to_word(value: u64) -> u32 { value as u32 }
+}
+
+// A decimal MIX: each byte holds 0 to 99, and words are held in a u64 whose bit 34 is
+// the sign
+pub struct Decimal;
+
+impl ByteSize for Decimal {
+    type Word = u64;
+    const BYTE_SIZE: u64 = 100;
+    const SIGN_BIT: u64 = 1u64 << 34;
+    const BINARY: bool = false;
+
+    fn from_word(word: u64) -> u64 { word }
+    fn to_word(value: u64) -> u64 { value }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_word() {
+        assert_eq!(Binary::make_word(false, [1, 2, 3, 4, 5]), (1u32 << 30) + (1u32 << 24) + (2u32 << 18) + (3u32 << 12) + (4u32 << 6) + 5u32);
+        assert_eq!(Decimal::make_word(false, [1, 2, 3, 4, 5]), (1u64 << 34) + 102030405u64);
+        assert_eq!(Decimal::make_word(true, [99, 99, 99, 99, 99]), 9999999999u64);
+    }
+
+    #[test]
+    fn test_split_instruction() {
+        let instruction = Decimal::make_instruction(false, 2000, 3, 13, 8);
+        assert_eq!(instruction, (1u64 << 34) + 2000031308u64);
+        assert_eq!(Decimal::split_instruction(instruction), (true, 2000, 3, 13, 8));
+        assert_eq!(Binary::split_instruction(Binary::make_instruction(true, 4095, 6, 45, 63) as u64), (false, 4095, 6, 45, 63));
+    }
+
+    #[test]
+    fn test_signed_conversion() {
+        assert_eq!(Decimal::to_signed((1u64 << 34) + 12), -12i64);
+        assert_eq!(Decimal::from_signed(-12i64), (1u64 << 34) + 12);
+        assert_eq!(Binary::from_signed(-12i64), (1u64 << 30) + 12);
+        assert_eq!(Binary::to_signed(12), 12i64);
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(Binary::is_valid((1u64 << 31) - 1));
+        assert!(!Binary::is_valid(1u64 << 31));
+        assert!(Decimal::is_valid((1u64 << 34) + 9999999999u64));
+        assert!(!Decimal::is_valid(10000000000u64));
+    }
+}
@@ -0,0 +1,544 @@
+// An assembler for MIXAL, the assembly language of TAOCP 1.3.2.
+//
+// Source is read in free format: each line is an optional label, an operation and an
+// operand, separated by blanks, and anything after the operand is a comment. A line
+// that starts with a blank has no label, and a line that starts with '*' is a comment.
+// The operand of ALF is the five characters after the single blank that follows ALF,
+// or may be given in double quotes.
+//
+// Instructions take the operand ADDRESS,INDEX(FIELD), where each part may be left out.
+// The pseudo-operations are ORIG, EQU, CON, ALF and END, which take a W-value.
+//
+// Expressions are built from numbers, symbols and * (the location counter), combined
+// with the binary operators + - * / // and :, and are evaluated strictly from left to
+// right. Each operation gives the result MIX arithmetic would: the sum, difference or
+// product keeps its sign and only the low five bytes, u/v truncates like DIV, u//v is
+// the fraction u*b^5/v for byte size b, and u:v is 8u+v. A W-value E1(F1),E2(F2),...
+// is built by storing each Ei into field Fi of a word that starts as +0, as STA does.
+// A field left out is (0:5). Values are carried as signed words, so -0 stays -0.
+//
+// The local symbols 0H to 9H may be defined any number of times. dB refers to the most
+// recent dH, and dF to the next one. The address of an instruction may also be a
+// future reference: dF, or a symbol defined later. Future references are assembled
+// as 0 and patched by a fix-up pass at the end, so they may not appear anywhere else,
+// including as part of a larger expression.
+//
+// The address of an instruction may also be a literal constant =W-value=. Each different
+// literal is given a word of its own, placed after the program when END is reached.
+// A symbol that is used as an address but never defined is then given a word of +0
+// after the literals, as if by SYMBOL CON 0. A label on END comes after all of these.
+//
+// The result is a memory image together with the start address given by END, and a
+// listing showing where each line was assembled.
+
+use mix_device::MAX_UNIT;
+use mix_instruction_set::{InstructionDef, FieldKind, OperandClass};
+use mix_machine::{GenericMixMachine, MixMachineErr};
+use mix_operations::Operation;
+use mix_char::text_to_word;
+use mix_word::ByteSize;
+use std::collections::HashMap;
+
+const MAX_SYMBOL_LENGTH: usize = 10;
+
+#[derive(PartialEq, Debug)]
+pub struct MixalErr {
+    pub line: usize,    // Numbered from 1
+    pub message: String,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ListingLine<W> {
+    pub line: usize,
+    pub location: Option<u16>,  // Where the word of this line was assembled, if any
+    pub word: Option<W>,
+    pub source: String,
+}
+
+pub struct Program<W> {
+    pub words: Vec<(u16, W)>,   // The location and contents of each assembled word
+    pub start: u16,
+    pub listing: Vec<ListingLine<W>>,
+}
+
+impl<W: Copy> Program<W> {
+    // Put the program into memory, and set the machine to start at its start address
+    pub fn load<B: ByteSize<Word = W>>(&self, mix_machine: &mut GenericMixMachine<B>) -> Result<(), MixMachineErr> {
+        for &(location, word) in self.words.iter() {
+            mix_machine.poke_memory(location, word)?;
+        }
+        mix_machine.set_program_counter(self.start)
+    }
+}
+
+pub fn assemble<B: ByteSize>(source: &str) -> Result<Program<B::Word>, MixalErr> {
+    let mut assembler = Assembler::<B>::new();
+    let mut start = None;
+    for (i, text) in source.lines().enumerate() {
+        let line = i + 1;
+        let error = |message: String| MixalErr{line, message};
+        let assembled = assembler.assemble_line(text).map_err(error)?;
+        assembler.listing.push(ListingLine{line, location: assembled.map(|(location, _)| location),
+                                           word: assembled.map(|(_, word)| word), source: String::from(text)});
+        if let Some(end) = assembler.end {
+            let label = split_line(text).and_then(|line| line.label);
+            assembler.finish(line, label).map_err(error)?;
+            start = Some(end);
+            break;
+        }
+    }
+    match start {
+        Some(start) => {
+            assembler.fix_up()?;
+            Ok(Program{words: assembler.words, start, listing: assembler.listing})
+        },
+        None => Err(MixalErr{line: source.lines().count(), message: String::from("Missing END.")}),
+    }
+}
+
+struct Assembler<B: ByteSize> {
+    symbols: HashMap<String, u64>,  // Signed words, so that a symbol may be -0
+    location: i64,
+    words: Vec<(u16, B::Word)>,
+    listing: Vec<ListingLine<B::Word>>,
+    end: Option<u16>,
+    fix_ups: Vec<FixUp>,
+    literals: Vec<(String, B::Word)>,   // Each different literal, as first written
+}
+
+// What the address of an instruction refers forward to
+#[derive(PartialEq)]
+enum Target {
+    Symbol(String),     // The label that will define it
+    Literal(usize),     // An index in literals
+}
+
+// A future reference or literal as written, and what it refers to
+type Reference = (String, Target);
+
+// An instruction whose address refers forward, to be patched once the target is placed
+struct FixUp {
+    reference: String,      // As written, e.g. LOOP, 2F or =1000=
+    target: Target,
+    address: Option<u64>,
+    word: usize,            // Index of the instruction in words
+    listing: usize,         // Index of its line in listing
+}
+
+// A line split into its label, operation and operand
+struct SourceLine<'a> {
+    label: Option<&'a str>,
+    op: &'a str,
+    operand: &'a str,
+}
+
+// The first blank-separated field of text, and everything after it
+fn split_field(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    text.split_at(end)
+}
+
+fn split_line(text: &str) -> Option<SourceLine<'_>> {
+    if text.trim().is_empty() || text.starts_with('*') {
+        return None;
+    }
+    let (label, rest) = if text.starts_with(char::is_whitespace) { (None, text) } else {
+        let (label, rest) = split_field(text);
+        (Some(label), rest)
+    };
+    let (op, rest) = split_field(rest);
+    // The operand of ALF may contain blanks, so it is kept as the rest of the line
+    let operand = if op == "ALF" { rest } else { split_field(rest).0 };
+    Some(SourceLine{label, op, operand})
+}
+
+// The digit and kind (H, B or F) of a local symbol
+fn local_symbol(text: &str) -> Option<(char, char)> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next(), chars.next()) {
+        (Some(digit), Some(kind), None) if digit.is_ascii_digit() && (kind == 'H' || kind == 'B' || kind == 'F') => Some((digit, kind)),
+        _ => None,
+    }
+}
+
+fn is_symbol(text: &str) -> bool {
+    !text.is_empty() && text.len() <= MAX_SYMBOL_LENGTH
+        && text.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        && text.chars().any(|c| c.is_ascii_uppercase())
+}
+
+// A binary operation of an expression, giving the result MIX arithmetic would. Only
+// the five low bytes of a sum, difference or product are kept, as in rA after ADD or
+// rX after MUL. A quotient that would not fit in rA is an error. A zero sum or
+// difference keeps the sign of u, as rA does, and any other zero result has the
+// product of the signs, as after MUL or DIV.
+fn operate<B: ByteSize>(u: u64, op: &str, v: u64) -> Result<u64, &'static str> {
+    let zero_is_negative = match op {
+        "*" | "/" | "//" => (B::sign(u) == 0) != (B::sign(v) == 0),
+        _ => B::sign(u) != 0,
+    };
+    let (u, v, word_size) = (B::to_signed(u) as i128, B::to_signed(v) as i128, B::power(5) as i128);
+    let result = match op {
+        "+" => u + v,
+        "-" => u - v,
+        "*" => u * v,
+        "/" | "//" if v == 0 => return Err("Division by zero"),
+        "/" => u / v,
+        "//" if u.abs() >= v.abs() => return Err("Overflow"),
+        "//" => u * word_size / v,
+        _ => 8 * u + v,
+    };
+    let magnitude = (result.abs() % word_size) as u64;
+    let negative = if result == 0 { zero_is_negative } else { result < 0 };
+    Ok(if negative { B::SIGN_BIT | magnitude } else { magnitude })
+}
+
+impl<B: ByteSize> Assembler<B> {
+    fn new() -> Assembler<B> {
+        Assembler{symbols: HashMap::new(), location: 0, words: Vec::new(), listing: Vec::new(), end: None, fix_ups: Vec::new(),
+                  literals: Vec::new()}
+    }
+
+    // Assemble one line, returning the location and contents of the word it produced
+    fn assemble_line(&mut self, text: &str) -> Result<Option<(u16, B::Word)>, String> {
+        let line = match split_line(text) {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        if line.op.is_empty() {
+            return Err(String::from("Missing operation."));
+        }
+        if line.op == "EQU" {
+            let value = B::from_word(self.word_value(line.operand)?);
+            self.define(line.label, value)?;
+            return Ok(None);
+        }
+        // The label is defined only once the operand has been evaluated, so that 2B in
+        // the operand refers to an earlier 2H and not to this line
+        let location = B::from_signed(self.location);
+        match line.op {
+            "ORIG" => {
+                let origin = self.signed_value(line.operand)?;
+                self.define(line.label, location)?;
+                self.location = origin;
+                Ok(None)
+            },
+            "END" => {
+                let start = self.signed_value(line.operand)?;
+                self.end = Some(self.check_location(start)?);     // The label is defined by finish()
+                Ok(None)
+            },
+            "CON" => {
+                let word = self.word_value(line.operand)?;
+                self.define(line.label, location)?;
+                self.emit(word).map(Some)
+            },
+            "ALF" => {
+                let word = self.alf(line.operand)?;
+                self.define(line.label, location)?;
+                self.emit(word).map(Some)
+            },
+            op => {
+                let (word, future) = self.instruction(op, line.operand)?;
+                self.define(line.label, location)?;
+                let assembled = self.emit(word)?;
+                if let Some((reference, target)) = future {
+                    // The address may be the symbol this line has just defined
+                    let address = match target {
+                        Target::Symbol(ref symbol) if local_symbol(symbol).is_none() => self.symbols.get(symbol).cloned(),
+                        _ => None,
+                    };
+                    self.fix_ups.push(FixUp{reference, target, address,
+                                            word: self.words.len() - 1, listing: self.listing.len()});
+                }
+                Ok(Some(assembled))
+            },
+        }
+    }
+
+    fn define(&mut self, label: Option<&str>, value: u64) -> Result<(), String> {
+        let label = match label {
+            Some(label) => label,
+            None => return Ok(()),
+        };
+        // Only dH may be defined more than once
+        let local = match local_symbol(label) {
+            Some((_, 'H')) => true,
+            Some(_) => return Err(format!("Invalid label {}.", label)),
+            None => false,
+        };
+        if !local && !is_symbol(label) {
+            return Err(format!("Invalid symbol {}.", label));
+        }
+        if self.symbols.insert(String::from(label), value).is_some() && !local {
+            return Err(format!("Symbol {} is already defined.", label));
+        }
+        self.resolve(Target::Symbol(String::from(label)), value);
+        Ok(())
+    }
+
+    // Give the address of target to the instructions waiting for it
+    fn resolve(&mut self, target: Target, value: u64) {
+        for fix_up in self.fix_ups.iter_mut().filter(|fix_up| fix_up.target == target && fix_up.address.is_none()) {
+            fix_up.address = Some(value);
+        }
+    }
+
+    // The label a future reference waits for, if text is one
+    fn future_reference(&self, text: &str) -> Option<Target> {
+        match local_symbol(text) {
+            Some((digit, 'F')) => Some(Target::Symbol(format!("{}H", digit))),
+            Some(_) => None,
+            None if is_symbol(text) && !self.symbols.contains_key(text) => Some(Target::Symbol(String::from(text))),
+            None => None,
+        }
+    }
+
+    // The literal =value= as a target, sharing the word of any equal literal
+    fn literal(&mut self, text: &str) -> Result<Target, String> {
+        let word = self.word_value(&text[1..text.len() - 1])?;
+        let index = match self.literals.iter().position(|&(_, w)| w == word) {
+            Some(index) => index,
+            None => {
+                self.literals.push((String::from(text), word));
+                self.literals.len() - 1
+            },
+        };
+        Ok(Target::Literal(index))
+    }
+
+    // Place the literals after the program, listing them after the END line
+    fn pool_literals(&mut self, line_number: usize) -> Result<(), String> {
+        for i in 0..self.literals.len() {
+            let (source, word) = self.literals[i].clone();
+            let (location, word) = self.emit(word)?;
+            self.listing.push(ListingLine{line: line_number, location: Some(location), word: Some(word), source});
+            self.resolve(Target::Literal(i), location as u64);
+        }
+        Ok(())
+    }
+
+    // Place the words that go before END, as though their lines came just before it, and
+    // then give the label of END the location that follows them
+    fn finish(&mut self, line_number: usize, label: Option<&str>) -> Result<(), String> {
+        self.pool_literals(line_number)?;
+        self.pool_undefined_symbols(line_number, label)?;
+        let location = B::from_signed(self.location);
+        self.define(label, location)
+    }
+
+    // Give each symbol that was used but never defined a word of +0 after the literals,
+    // as if it had been defined by SYMBOL CON 0. The label of END is defined later.
+    fn pool_undefined_symbols(&mut self, line_number: usize, end_label: Option<&str>) -> Result<(), String> {
+        let mut undefined: Vec<String> = Vec::new();
+        for fix_up in self.fix_ups.iter().filter(|fix_up| fix_up.address.is_none()) {
+            match fix_up.target {
+                Target::Symbol(ref symbol) if local_symbol(symbol).is_none() && !undefined.contains(symbol)
+                                              && end_label != Some(symbol.as_str()) => undefined.push(symbol.clone()),
+                _ => {},
+            }
+        }
+        for symbol in undefined {
+            let (location, word) = self.emit(B::to_word(0u64))?;
+            self.listing.push(ListingLine{line: line_number, location: Some(location), word: Some(word), source: symbol.clone()});
+            self.define(Some(&symbol), location as u64)?;
+        }
+        Ok(())
+    }
+
+    // Patch the address of every instruction that referred forward
+    fn fix_up(&mut self) -> Result<(), MixalErr> {
+        for fix_up in self.fix_ups.iter() {
+            let line = self.listing[fix_up.listing].line;
+            let address = match fix_up.address {
+                Some(address) if B::magnitude(address) >= B::power(2) => {
+                    return Err(MixalErr{line, message: format!("Address {} is too large.", B::to_signed(address))});
+                },
+                Some(address) => address,
+                None => return Err(MixalErr{line, message: format!("Undefined symbol {}.", fix_up.reference)}),
+            };
+            let (location, word) = self.words[fix_up.word];
+            let (_, _, index_spec, field_spec, op_code) = B::split_instruction(B::from_word(word));
+            let word = B::make_instruction(B::sign(address) == 0, B::magnitude(address) as u16, index_spec, field_spec, op_code);
+            self.words[fix_up.word] = (location, word);
+            self.listing[fix_up.listing].word = Some(word);
+        }
+        Ok(())
+    }
+
+    fn check_location(&self, location: i64) -> Result<u16, String> {
+        if location < 0 || location as u64 >= B::power(2) {
+            Err(format!("Location {} is outside memory.", location))
+        } else {
+            Ok(location as u16)
+        }
+    }
+
+    // Place a word at the location counter, and advance it
+    fn emit(&mut self, word: B::Word) -> Result<(u16, B::Word), String> {
+        let location = self.check_location(self.location)?;
+        self.words.push((location, word));
+        self.location += 1;
+        Ok((location, word))
+    }
+
+    // The word given by a W-value E1(F1),E2(F2),...
+    fn word_value(&self, text: &str) -> Result<B::Word, String> {
+        let mut word = 0u64;
+        for part in text.split(',') {
+            let (expression, field) = match part.find('(') {
+                Some(i) if part.ends_with(')') => (&part[..i], B::to_signed(self.expression(&part[i + 1..part.len() - 1])?)),
+                Some(_) => return Err(format!("Invalid W-value {}.", text)),
+                None => (part, 5),
+            };
+            if field < 0 || field as u64 >= B::BYTE_SIZE {
+                return Err(format!("Invalid field {} in {}.", field, text));
+            }
+            let value = self.expression(expression)?;
+            word = GenericMixMachine::<B>::embed_from_field(value, word, field as u8).map_err(|e| e.message)?;
+        }
+        Ok(B::to_word(word))
+    }
+
+    fn signed_value(&self, text: &str) -> Result<i64, String> {
+        self.word_value(text).map(|word| B::to_signed(B::from_word(word)))
+    }
+
+    fn alf(&self, text: &str) -> Result<B::Word, String> {
+        // Skip the blank separating ALF from its operand
+        let text = text.strip_prefix(' ').unwrap_or(text);
+        let characters: String = match text.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => String::from(&quoted[..end]),
+                None => return Err(String::from("Unterminated ALF string.")),
+            },
+            None => text.chars().take(5).collect(),
+        };
+        text_to_word::<B>(&characters).map_err(|e| e.message)
+    }
+
+    // An instruction with the operand ADDRESS,INDEX(FIELD), and its address if that is a
+    // future reference or a literal
+    fn instruction(&mut self, op: &str, operand: &str) -> Result<(B::Word, Option<Reference>), String> {
+        let def = match InstructionDef::from_mnemonic(op) {
+            Some(def) => def,
+            None => return Err(format!("Unknown operation {}.", op)),
+        };
+        // A literal may itself contain commas and parentheses
+        let (address, rest) = match operand.strip_prefix('=') {
+            Some(literal) => match literal.find('=') {
+                Some(end) => operand.split_at(end + 2),
+                None => return Err(format!("Unterminated literal {}.", operand)),
+            },
+            None => operand.split_at(operand.find(&[',', '('][..]).unwrap_or(operand.len())),
+        };
+        let (index, field) = match rest.find('(') {
+            Some(i) if rest.ends_with(')') => (&rest[..i], Some(&rest[i + 1..rest.len() - 1])),
+            Some(_) => return Err(format!("Invalid operand {}.", operand)),
+            None => (rest, None),
+        };
+        let index = match index.strip_prefix(',') {
+            Some(index) => Some(index),
+            None if index.is_empty() => None,
+            None => return Err(format!("Invalid operand {}.", operand)),
+        };
+        let future = if address.starts_with('=') {
+            Some(self.literal(address)?)
+        } else {
+            self.future_reference(address)
+        };
+        let reference = future.map(|target| (String::from(address), target));
+        let address = if address.is_empty() || reference.is_some() { 0 } else { self.expression(address)? };
+        if B::magnitude(address) >= B::power(2) {
+            return Err(format!("Address {} is too large.", B::to_signed(address)));
+        }
+        let index = match index {
+            Some(index) => B::to_signed(self.expression(index)?),
+            None => 0,
+        };
+        if index < 0 || index as u64 >= B::BYTE_SIZE {
+            return Err(format!("Invalid index {}.", index));
+        }
+        let field = match field {
+            Some(field) => Some(self.field(def, field)?),
+            None => None,
+        };
+        Operation::encode::<B>(op, B::sign(address) == 0, B::magnitude(address) as u16, index as u8, field)
+            .map(|word| (word, reference))
+            .map_err(|_| format!("Invalid field for {}.", op))
+    }
+
+    // The field of an instruction, which for a partial field must be L:R with L<=R<=5,
+    // and for I/O must be a unit number
+    fn field(&self, def: &InstructionDef, text: &str) -> Result<u8, String> {
+        let value = B::to_signed(self.expression(text)?);
+        let (left, right) = (value / 8, value % 8);
+        if value < 0 || value as u64 >= B::BYTE_SIZE
+            || (def.field_kind() == FieldKind::Partial && (left > right || right > 5)) {
+            Err(format!("Invalid field {}.", text))
+        } else if matches!(def.class, OperandClass::InputOutput(_)) && value > MAX_UNIT as i64 {
+            Err(format!("Invalid unit {}.", text))
+        } else {
+            Ok(value as u8)
+        }
+    }
+
+    // Evaluate an expression strictly from left to right, giving a signed word
+    fn expression(&self, text: &str) -> Result<u64, String> {
+        if text.is_empty() {
+            return Err(String::from("Missing expression."));
+        }
+        let (mut value, mut rest) = match text.strip_prefix('-') {
+            Some(rest) => self.atom(rest).map(|(atom, rest)| (atom ^ B::SIGN_BIT, rest))?,
+            None => self.atom(text.strip_prefix('+').unwrap_or(text))?,
+        };
+        while !rest.is_empty() {
+            // // must be tried before /
+            let op = match ["//", "+", "-", "*", "/", ":"].iter().find(|&op| rest.starts_with(op)) {
+                Some(op) => *op,
+                None => return Err(format!("Invalid expression {}.", text)),
+            };
+            if rest.len() == op.len() {
+                return Err(format!("Invalid expression {}.", text));
+            }
+            let (atom, after_atom) = self.atom(&rest[op.len()..])?;
+            value = operate::<B>(value, op, atom).map_err(|message| format!("{} in {}.", message, text))?;
+            rest = after_atom;
+        }
+        Ok(value)
+    }
+
+    // A number, a symbol, or * for the location counter, at the start of text
+    fn atom<'a>(&self, text: &'a str) -> Result<(u64, &'a str), String> {
+        if let Some(rest) = text.strip_prefix('*') {
+            return Ok((B::from_signed(self.location), rest));
+        }
+        let end = text.find(|c: char| !(c.is_ascii_uppercase() || c.is_ascii_digit())).unwrap_or(text.len());
+        let (atom, rest) = text.split_at(end);
+        if atom.is_empty() {
+            Err(format!("Missing value in {}.", text))
+        } else if atom.chars().all(|c| c.is_ascii_digit()) {
+            match atom.parse::<u64>() {
+                Ok(n) if n < B::power(5) => Ok((n, rest)),
+                _ => Err(format!("Number {} does not fit in a word.", atom)),
+            }
+        } else if let Some((digit, kind)) = local_symbol(atom) {
+            match kind {
+                'B' => match self.symbols.get(&format!("{}H", digit)) {
+                    Some(&value) => Ok((value, rest)),
+                    None => Err(format!("Undefined symbol {}.", atom)),
+                },
+                'F' => Err(format!("Future reference {} is only allowed as the address of an instruction.", atom)),
+                _ => Err(format!("Local symbol {} can only be used as a label.", atom)),
+            }
+        } else if is_symbol(atom) {
+            match self.symbols.get(atom) {
+                Some(&value) => Ok((value, rest)),
+                None => Err(format!("Future reference {} is only allowed as the address of an instruction.", atom)),
+            }
+        } else {
+            Err(format!("Invalid symbol {}.", atom))
+        }
+    }
+}
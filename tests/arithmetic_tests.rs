@@ -255,6 +255,7 @@ fn test_division_negative_everything() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn test_division_large_with_no_overflow() {
     let mut mix_machine = MixMachine::new();
     assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 4u8)), Ok(())); // Divide [A, X] by CONTENTS(10) and store in A
@@ -270,6 +271,7 @@ fn test_division_large_with_no_overflow() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn test_division_large_with_overflow() {
     let mut mix_machine = MixMachine::new();
     assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 4u8)), Ok(())); // Divide [A, X] by CONTENTS(10) and store in A
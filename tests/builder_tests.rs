@@ -0,0 +1,82 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_word::*;
+
+#[test]
+fn default_builder_matches_new() {
+    let mix_machine = MixMachine::builder().build().unwrap();
+    assert_eq!(mix_machine.memory_size(), MixMachine::new().memory_size());
+    assert_eq!(mix_machine.memory_size(), 4000u16);
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(0u32));
+}
+
+#[test]
+fn larger_memory() {
+    let mut mix_machine = MixMachine::builder().memory_size(4096).build().unwrap();
+    assert_eq!(mix_machine.poke_memory(4095u16, 17u32), Ok(()));
+    assert_eq!(mix_machine.poke_memory(4096u16, 17u32), Err(MixMachineErr{message: String::from("Attempt to access invalid memory address 4096.")}));
+
+    let mut mix_machine = DecimalMixMachine::builder().memory_size(10000).build().unwrap();
+    assert_eq!(mix_machine.poke_memory(0u16, Decimal::make_instruction(true, 9999, 0, 5, 8)), Ok(()));   // LDA 9999
+    assert_eq!(mix_machine.poke_memory(9999u16, 17u64), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(17u64));
+}
+
+#[test]
+fn invalid_memory_size() {
+    assert_eq!(MixMachine::builder().memory_size(4097).build().err(),
+               Some(MixMachineErr{message: String::from("Invalid memory size 4097. Must be between 1 and 4096.")}));
+    assert!(MixMachine::builder().memory_size(0).build().is_err());
+}
+
+#[test]
+fn smaller_memory() {
+    let mut mix_machine = MixMachine::builder().memory_size(2).build().unwrap();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 0u8)), Ok(())); // NOP
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 0u8)), Ok(())); // NOP
+    assert_eq!(mix_machine.run(), Err(MixMachineErr{message: String::from("Attempted instruction fetch from invalid memory address 2.")}));
+}
+
+#[test]
+fn initial_registers_and_program_counter() {
+    let mut mix_machine = MixMachine::builder()
+        .register(Register::RegA, 5u32)
+        .register(Register::RegI2, 3u32 + (1u32 << 30))
+        .program_counter(100)
+        .build().unwrap();
+    assert_eq!(mix_machine.peek_register(Register::RegI2), Ok(3u32 + (1u32 << 30)));
+    assert_eq!(mix_machine.poke_memory(100u16, Operation::make_instruction(true, 10u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 10
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(10u32));
+}
+
+#[test]
+fn invalid_initial_state() {
+    assert_eq!(MixMachine::builder().program_counter(4000).build().err(),
+               Some(MixMachineErr{message: String::from("Initial program counter 4000 is outside memory.")}));
+    assert!(MixMachine::builder().register(Register::RegI1, 1u32 << 12).build().is_err());
+}
+
+#[test]
+fn disabled_features() {
+    let mut mix_machine = MixMachine::builder().floating_point(false).interrupts(false).binary_extensions(false).build().unwrap();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 6u8, 5u8)), Ok(()));  // FLOT
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(()));  // INT
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 1u16, 0u8, 6u8, 6u8)), Ok(()));  // SLB 1
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 0u16, 0u8, 6u8, 40u8)), Ok(())); // JAE 0
+    for (location, instruction) in ["FLOT 0", "INT 0", "SLB 1", "JAE 0"].iter().enumerate() {
+        assert_eq!(mix_machine.step(), Err(MixMachineErr{message: format!("Illegal instruction {} at location {}: instruction not available on this machine.",
+                                                                          instruction, location)}));
+        assert_eq!(mix_machine.set_program_counter(location as u16 + 1), Ok(()));
+    }
+    assert!(mix_machine.request_interrupt(-20).is_err());
+}
+
+#[test]
+fn binary_extensions_need_binary_machine() {
+    assert!(DecimalMixMachine::builder().build().is_ok());
+    assert_eq!(DecimalMixMachine::builder().binary_extensions(true).build().err(),
+               Some(MixMachineErr{message: String::from("Binary extensions are only available on a binary MIX.")}));
+}
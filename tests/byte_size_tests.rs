@@ -0,0 +1,178 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_word::*;
+
+// Each program is run on both the binary and the decimal machine, to check that
+// programs which make no assumption about the byte size behave the same on both.
+
+type Instruction = (bool, u16, u8, u8, u8);
+
+fn load_program<B: ByteSize>(mix_machine: &mut GenericMixMachine<B>, program: &[Instruction]) {
+    for (i, &(positive, address, index_spec, field_spec, op_code)) in program.iter().enumerate() {
+        assert_eq!(mix_machine.poke_memory(i as u16, B::make_instruction(positive, address, index_spec, field_spec, op_code)), Ok(()));
+    }
+}
+
+fn register_value<B: ByteSize>(mix_machine: &GenericMixMachine<B>, reg: Register) -> i64 {
+    B::to_signed(B::from_word(mix_machine.peek_register(reg).unwrap()))
+}
+
+fn register_bytes<B: ByteSize>(mix_machine: &GenericMixMachine<B>, reg: Register) -> [u64; 5] {
+    let value = B::from_word(mix_machine.peek_register(reg).unwrap());
+    [B::byte(value, 1), B::byte(value, 2), B::byte(value, 3), B::byte(value, 4), B::byte(value, 5)]
+}
+
+fn sum_loop<B: ByteSize>() {
+    let mut mix_machine = GenericMixMachine::<B>::new();
+    load_program(&mut mix_machine, &[
+        (true, 100, 0, 2, 49),  // ENT1 100
+        (true, 0, 0, 2, 48),    // ENTA 0
+        (true, 0, 1, 0, 48),    // INCA 0,1
+        (true, 1, 0, 1, 49),    // DEC1 1
+        (true, 2, 0, 2, 41),    // J1P 2
+        (true, 0, 0, 2, 5),     // HLT
+    ]);
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(register_value(&mix_machine, Register::RegA), 5050);
+    assert_eq!(register_value(&mix_machine, Register::RegI1), 0);
+}
+
+#[test]
+fn sum_loop_on_both_machines() {
+    sum_loop::<Binary>();
+    sum_loop::<Decimal>();
+}
+
+fn multiply_and_divide<B: ByteSize>() {
+    let mut mix_machine = GenericMixMachine::<B>::new();
+    load_program(&mut mix_machine, &[
+        (true, 100, 0, 5, 8),   // LDA 100
+        (true, 101, 0, 5, 3),   // MUL 101
+        (true, 102, 0, 5, 4),   // DIV 102
+        (true, 0, 0, 2, 5),     // HLT
+    ]);
+    assert_eq!(mix_machine.poke_memory(100, B::to_word(B::from_signed(-123456))), Ok(()));
+    assert_eq!(mix_machine.poke_memory(101, B::to_word(789)), Ok(()));
+    assert_eq!(mix_machine.poke_memory(102, B::to_word(1000)), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(register_value(&mix_machine, Register::RegA), -97406);
+    assert_eq!(register_value(&mix_machine, Register::RegX), -784);
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(false));
+}
+
+#[test]
+fn multiply_and_divide_on_both_machines() {
+    multiply_and_divide::<Binary>();
+    multiply_and_divide::<Decimal>();
+}
+
+#[allow(clippy::identity_op)]
+fn fields_and_shifts<B: ByteSize>() {
+    let mut mix_machine = GenericMixMachine::<B>::new();
+    load_program(&mut mix_machine, &[
+        (true, 100, 0, 8*2 + 3, 8),     // LDA 100(2:3)
+        (true, 100, 0, 5, 15),          // LDX 100
+        (true, 3, 0, 2, 6),             // SLAX 3
+        (true, 101, 0, 8*1 + 2, 24),    // STA 101(1:2)
+        (true, 4, 0, 4, 6),             // SLC 4
+        (true, 0, 0, 2, 5),             // HLT
+    ]);
+    assert_eq!(mix_machine.poke_memory(100, B::make_word(false, [1, 2, 3, 4, 5])), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    // After SLAX 3 rA is + 2 3 1 2 3 and rX is - 4 5 0 0 0, then SLC 4 rotates rAX by 4
+    assert_eq!(register_bytes(&mix_machine, Register::RegA), [3, 4, 5, 0, 0]);
+    assert_eq!(register_bytes(&mix_machine, Register::RegX), [0, 2, 3, 1, 2]);
+    assert!(register_value(&mix_machine, Register::RegX) < 0);
+    assert_eq!(mix_machine.peek_memory(101), Ok(B::make_word(true, [2, 3, 0, 0, 0])));
+}
+
+#[test]
+fn fields_and_shifts_on_both_machines() {
+    fields_and_shifts::<Binary>();
+    fields_and_shifts::<Decimal>();
+}
+
+fn char_and_num<B: ByteSize>() {
+    let mut mix_machine = GenericMixMachine::<B>::new();
+    load_program(&mut mix_machine, &[
+        (true, 0, 0, 1, 5),     // CHAR
+        (true, 0, 0, 5, 15),    // LDX 0, so that the digits are rebuilt from a fresh rX
+        (true, 0, 0, 0, 5),     // NUM
+        (true, 0, 0, 2, 5),     // HLT
+    ]);
+    assert_eq!(mix_machine.poke_register(Register::RegA, B::to_word(B::from_signed(-123456789))), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(register_bytes(&mix_machine, Register::RegA), [30, 31, 32, 33, 34]);
+    assert_eq!(register_bytes(&mix_machine, Register::RegX), [35, 36, 37, 38, 39]);
+    assert_eq!(mix_machine.run(), Ok(()));
+    // rX now holds the instruction CHAR, whose bytes 0 0 0 1 5 give the last five digits
+    assert_eq!(register_value(&mix_machine, Register::RegA), -123400015);
+}
+
+#[test]
+fn char_and_num_on_both_machines() {
+    char_and_num::<Binary>();
+    char_and_num::<Decimal>();
+}
+
+fn floating_point<B: ByteSize>() {
+    let mut mix_machine = GenericMixMachine::<B>::new();
+    load_program(&mut mix_machine, &[
+        (true, 3, 0, 2, 48),    // ENTA 3
+        (true, 0, 0, 6, 5),     // FLOT
+        (true, 100, 0, 5, 24),  // STA 100
+        (true, 100, 0, 6, 3),   // FMUL 100
+        (true, 100, 0, 6, 1),   // FADD 100
+        (true, 0, 0, 7, 5),     // FIX
+        (true, 0, 0, 2, 5),     // HLT
+    ]);
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(register_value(&mix_machine, Register::RegA), 12);
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(false));
+}
+
+#[test]
+fn floating_point_on_both_machines() {
+    floating_point::<Binary>();
+    floating_point::<Decimal>();
+}
+
+fn overflow<B: ByteSize>() {
+    let mut mix_machine = GenericMixMachine::<B>::new();
+    load_program(&mut mix_machine, &[
+        (true, 100, 0, 5, 8),   // LDA 100
+        (true, 101, 0, 5, 1),   // ADD 101
+        (true, 0, 0, 2, 5),     // HLT
+    ]);
+    assert_eq!(mix_machine.poke_memory(100, B::to_word(B::power(5) - 1)), Ok(()));
+    assert_eq!(mix_machine.poke_memory(101, B::to_word(2)), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(register_value(&mix_machine, Register::RegA), 1);
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(true));
+}
+
+#[test]
+fn overflow_on_both_machines() {
+    overflow::<Binary>();
+    overflow::<Decimal>();
+}
+
+#[test]
+fn decimal_words() {
+    let mut mix_machine = DecimalMixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0, Decimal::make_instruction(true, 3999, 0, 5, 8)), Ok(()));    // LDA 3999
+    assert_eq!(mix_machine.poke_memory(3999, 9999999999u64), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(9999999999u64));
+    assert_eq!(mix_machine.poke_memory(1, 10000000000u64), Err(MixMachineErr{message: String::from("Attempt to write invalid value 10000000000.")}));
+}
+
+#[test]
+fn binary_instructions_on_decimal_machine() {
+    let mut mix_machine = DecimalMixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0, Decimal::make_instruction(true, 1, 0, 6, 6)), Ok(()));   // SLB 1
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction SLB 1 at location 0: instruction not available on this machine.")}));
+    assert_eq!(mix_machine.poke_memory(1, Decimal::make_instruction(true, 0, 0, 6, 40)), Ok(()));  // JAE 0
+    assert_eq!(mix_machine.set_program_counter(1), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction JAE 0 at location 1: instruction not available on this machine.")}));
+}
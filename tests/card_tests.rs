@@ -0,0 +1,63 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_card::*;
+use mix::mix_char::*;
+use mix::mix_word::*;
+use std::fs;
+use std::io::Cursor;
+use std::process;
+
+fn machine_with_deck(deck: &str) -> MixMachine {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(16, Box::new(CardReader::new(Box::new(Cursor::new(String::from(deck)))))), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    mix_machine
+}
+
+#[test]
+fn read_card() {
+    let mut mix_machine = machine_with_deck("HELLO, world 09\n");
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(100u16), text_to_word::<Binary>("HELLO"));
+    assert_eq!(mix_machine.peek_memory(101u16), text_to_word::<Binary>(", WOR"));
+    assert_eq!(mix_machine.peek_memory(102u16), text_to_word::<Binary>("LD 09"));
+    for i in 103u16..116 {
+        assert_eq!(mix_machine.peek_memory(i), Ok(0u32));
+    }
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 16: No more cards in the reader.")}));
+}
+
+#[test]
+fn unreadable_card() {
+    let mut mix_machine = machine_with_deck("ABC\n#\n");
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 16: Card 2: Character '#' is not in the MIX character set.")}));
+}
+
+#[test]
+fn overlong_card() {
+    let mut mix_machine = machine_with_deck(&format!("{}\n", "X".repeat(81)));
+    assert!(mix_machine.step().is_err());
+}
+
+#[test]
+fn punch_cards() {
+    let path = std::env::temp_dir().join(format!("mix_punch_{}", process::id()));
+    let _ = fs::write(&path, "EXISTING CARD\n");
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(17, Box::new(CardPunch::open(&path).unwrap())), Ok(()));
+    assert_eq!(mix_machine.poke_memory(100u16, text_to_word::<Binary>("012 Σ").unwrap()), Ok(()));
+    assert_eq!(mix_machine.poke_memory(115u16, Binary::make_word(false, [0, 0, 0, 0, 55])), Ok(()));   // '
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 17u8, 37u8)), Ok(())); // OUT 100(17)
+    assert_eq!(mix_machine.step(), Ok(()));
+    let expected = format!("EXISTING CARD\n012 Σ{}'\n", " ".repeat(74));
+    assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+
+    assert_eq!(mix_machine.poke_memory(100u16, 63u32), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));    // The NOP at location 1
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 17u8, 37u8)), Ok(())); // OUT 100(17)
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 17: Character code 63 has no printable character.")}));
+    let _ = fs::remove_file(&path);
+}
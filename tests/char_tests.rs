@@ -0,0 +1,45 @@
+extern crate mix;
+use mix::mix_char::*;
+use mix::mix_word::*;
+
+#[test]
+fn round_trip_all_codes() {
+    for code in 0u8..56 {
+        let c = code_to_char(code).unwrap();
+        assert_eq!(char_to_code(c), Some(code));
+    }
+    assert_eq!(code_to_char(56), None);
+    assert_eq!(code_to_char(99), None);
+}
+
+#[test]
+fn special_characters() {
+    assert_eq!(char_to_code(' '), Some(0));
+    assert_eq!(char_to_code('Δ'), Some(10));
+    assert_eq!(char_to_code('Σ'), Some(20));
+    assert_eq!(char_to_code('Π'), Some(21));
+    assert_eq!(char_to_code('0'), Some(DIGIT_ZERO));
+    assert_eq!(char_to_code('\''), Some(55));
+    assert_eq!(char_to_code('\u{2019}'), Some(55));
+    assert_eq!(char_to_code('q'), char_to_code('Q'));
+    assert_eq!(char_to_code('#'), None);
+}
+
+#[test]
+fn words() {
+    assert_eq!(text_to_word::<Binary>("AB9"), Ok(Binary::make_word(true, [1, 2, 39, 0, 0])));
+    assert_eq!(text_to_word::<Decimal>("Σ'"), Ok(Decimal::make_word(true, [20, 55, 0, 0, 0])));
+    assert!(text_to_word::<Binary>("TOO LONG").is_err());
+    assert_eq!(word_to_text::<Binary>(Binary::make_word(false, [8, 5, 13, 13, 16])), Ok(String::from("HELLO")));
+    assert!(word_to_text::<Binary>(Binary::make_word(true, [8, 5, 13, 13, 63])).is_err());
+}
+
+#[test]
+fn strings() {
+    let words = text_to_words::<Binary>("MIX IS FUN", 3).unwrap();
+    assert_eq!(words.len(), 3);
+    assert_eq!(words_to_text::<Binary>(&words), Ok(String::from("MIX IS FUN     ")));
+    assert_eq!(words_to_printable::<Binary>(&words), "MIX IS FUN");
+    let words = vec![Binary::make_word(true, [1, 60, 0, 0, 0])];
+    assert_eq!(words_to_printable::<Binary>(&words), format!("A{}", UNPRINTABLE));
+}
@@ -49,6 +49,7 @@ fn compare_a_greater() {
 }
 
 #[test]
+#[allow(clippy::identity_op)]
 fn compare_a_equal() {
     let mut mix_machine = MixMachine::new();
     assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 56u8)), Ok(()));    // CMPA 10
@@ -104,6 +105,7 @@ fn compare_a_with_indexing() {
 }
  
 #[test]
+#[allow(clippy::identity_op)]
 fn compare_a_with_field_spec() {
     let mut mix_machine = MixMachine::new();
     assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 8u8 + 5u8, 56u8)), Ok(()));    // CMPA 10,(1:5)
@@ -0,0 +1,83 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_char::*;
+use mix::mix_word::*;
+
+fn make_word(positive: bool, b1: u32, b2: u32, b3: u32, b4: u32, b5: u32) -> u32 {
+    let sgn = if positive { 0u32 } else { 1u32 << 30 };
+    sgn + (b1 << 24) + (b2 << 18) + (b3 << 12) + (b4 << 6) + b5
+}
+
+#[test]
+fn num_char_knuth_example() {
+    // The example from TAOCP 1.3.1
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 5u8)), Ok(()));  // NUM 0
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 1u16, 0u8, 0u8, 48u8)), Ok(())); // INCA 1
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 0u16, 0u8, 1u8, 5u8)), Ok(()));  // CHAR 0
+    assert_eq!(mix_machine.poke_register(Register::RegA, make_word(false, 0, 0, 31, 32, 39)), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, make_word(true, 37, 57, 47, 30, 30)), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(12977700u32 + (1u32 << 30)));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(make_word(true, 37, 57, 47, 30, 30)));
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(12977699u32 + (1u32 << 30)));
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(text_to_word::<Binary>("00129").unwrap() + (1u32 << 30)));
+    assert_eq!(mix_machine.peek_register(Register::RegX), text_to_word::<Binary>("77699"));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(false));
+}
+
+#[test]
+fn num_overflow() {
+    // 9999999999 does not fit in five 6-bit bytes, so the remainder mod 2^30 is kept
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 5u8)), Ok(()));  // NUM 0
+    assert_eq!(mix_machine.poke_register(Register::RegA, make_word(true, 39, 39, 39, 39, 39)), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, make_word(false, 39, 39, 39, 39, 39)), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok((9999999999u64 % (1u64 << 30)) as u32));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(make_word(false, 39, 39, 39, 39, 39)));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(true));
+}
+
+#[test]
+fn num_ignores_tens() {
+    // Every byte is taken mod 10, so non-digit characters still give a digit
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 5u8)), Ok(()));  // NUM 0
+    assert_eq!(mix_machine.poke_register(Register::RegA, make_word(true, 0, 0, 0, 0, 0)), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, make_word(true, 0, 0, 1, 12, 63)), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(123u32));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(false));
+}
+
+#[test]
+fn char_zero() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 1u8, 5u8)), Ok(()));  // CHAR 0
+    assert_eq!(mix_machine.poke_register(Register::RegA, 1u32 << 30), Ok(()));    // -0
+    assert_eq!(mix_machine.poke_register(Register::RegX, make_word(false, 1, 2, 3, 4, 5)), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_word(false, 30, 30, 30, 30, 30)));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(make_word(false, 30, 30, 30, 30, 30)));
+}
+
+#[test]
+fn char_maximum() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 1u8, 5u8)), Ok(()));  // CHAR 0
+    assert_eq!(mix_machine.poke_register(Register::RegA, (1u32 << 30) - 1), Ok(()));  // 1073741823
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), text_to_word::<Binary>("10737"));
+    assert_eq!(mix_machine.peek_register(Register::RegX), text_to_word::<Binary>("41823"));
+}
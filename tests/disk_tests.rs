@@ -0,0 +1,70 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_device::*;
+use mix::mix_disk::*;
+use mix::mix_word::*;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+// A path for a disk image, removing any left by an earlier run
+fn image_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mix_disk_{}_{}", name, process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// Write block 7 from location 1000, then read it back into location 2000
+fn write_and_read_back(disk: DiskUnit) {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(9, Box::new(disk)), Ok(()));
+    for i in 0..100u16 {
+        assert_eq!(mix_machine.poke_memory(1000u16 + i, 3u32 * i as u32), Ok(()));
+    }
+    assert_eq!(mix_machine.poke_register(Register::RegX, 7u32), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 1000u16, 0u8, 9u8, 37u8)), Ok(())); // OUT 1000(9)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 35u8)), Ok(()));    // IOC 0(9)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 2000u16, 0u8, 9u8, 36u8)), Ok(())); // IN 2000(9)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    for i in 0..100u16 {
+        assert_eq!(mix_machine.peek_memory(2000u16 + i), Ok(3u32 * i as u32));
+    }
+}
+
+#[test]
+fn in_memory_disk() {
+    write_and_read_back(DiskUnit::in_memory());
+}
+
+#[test]
+fn image_disk() {
+    let path = image_path("image");
+    write_and_read_back(DiskUnit::open(&path).unwrap());
+    // The block survives in the image
+    let mut disk = DiskUnit::open(&path).unwrap();
+    assert_eq!(disk.block::<Binary>(7).unwrap()[99], 297u32);
+    assert_eq!(disk.block::<Binary>(6).unwrap(), vec![0u32; DISK_BLOCK_SIZE]);
+    assert_eq!(disk.block::<Binary>(1000).unwrap(), vec![0u32; DISK_BLOCK_SIZE]);
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn unwritten_blocks_are_zero() {
+    let mut disk = DiskUnit::in_memory();
+    let mut block = vec![5u64; DISK_BLOCK_SIZE];
+    assert_eq!(Device::<Decimal>::read(&mut disk, &mut block, 42u64), Ok(()));
+    assert_eq!(block, vec![0u64; DISK_BLOCK_SIZE]);
+    assert_eq!(disk.position(), 42);
+}
+
+#[test]
+fn seek() {
+    let mut disk = DiskUnit::in_memory();
+    assert_eq!(Device::<Binary>::control(&mut disk, 0, 12u32), Ok(()));
+    assert_eq!(disk.position(), 12);
+    assert!(Device::<Binary>::control(&mut disk, 1, 12u32).is_err());
+    assert_eq!(Device::<Binary>::control(&mut disk, 0, (1u32 << 30) + 1), Err(MixMachineErr{message: String::from("Invalid block number -1.")}));
+}
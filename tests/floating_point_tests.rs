@@ -0,0 +1,199 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+
+// A floating point word: exponent e (excess 32) followed by four fraction bytes
+fn make_float(positive: bool, e: u32, f1: u32, f2: u32, f3: u32, f4: u32) -> u32 {
+    let sgn = if positive { 0u32 } else { 1u32 << 30 };
+    sgn + (e << 24) + (f1 << 18) + (f2 << 12) + (f3 << 6) + f4
+}
+
+// Load rA from location 10, apply the operation with opcode op_code and F=6 to location 11
+fn run_float_op(op_code: u8, a: u32, v: u32) -> MixMachine {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 8u8)), Ok(()));     // LDA 10
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 11u16, 0u8, 6u8, op_code)), Ok(())); // FADD 11, etc.
+    assert_eq!(mix_machine.poke_memory(10u16, a), Ok(()));
+    assert_eq!(mix_machine.poke_memory(11u16, v), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    mix_machine
+}
+
+#[test]
+fn float_addition() {
+    // 1.0 + 2.0 = 3.0
+    let mix_machine = run_float_op(1u8, make_float(true, 33, 1, 0, 0, 0), make_float(true, 33, 2, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 33, 3, 0, 0, 0)));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(false));
+
+    // 63.0 + 1.0 = 64.0, which needs a larger exponent
+    let mix_machine = run_float_op(1u8, make_float(true, 33, 63, 0, 0, 0), make_float(true, 33, 1, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 34, 1, 0, 0, 0)));
+}
+
+#[test]
+fn float_addition_rounding() {
+    // The last fraction byte of 1.0 has weight 64^-3. Adding half of that lies exactly
+    // half way between two representable numbers, and rounds to even.
+    let mix_machine = run_float_op(1u8, make_float(true, 33, 1, 0, 0, 0), make_float(true, 29, 32, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 33, 1, 0, 0, 0)));
+
+    // Slightly more than half rounds up
+    let mix_machine = run_float_op(1u8, make_float(true, 33, 1, 0, 0, 0), make_float(true, 29, 32, 0, 0, 1));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 33, 1, 0, 0, 1)));
+
+    // One and a half also lies half way, and rounds to even
+    let mix_machine = run_float_op(1u8, make_float(true, 33, 1, 0, 0, 0), make_float(true, 30, 1, 32, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 33, 1, 0, 0, 2)));
+
+    // A much smaller operand has no effect
+    let mix_machine = run_float_op(1u8, make_float(true, 40, 1, 2, 3, 4), make_float(false, 20, 63, 63, 63, 63));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 40, 1, 2, 3, 4)));
+}
+
+#[test]
+fn float_subtraction() {
+    // 1.0 - 2.0 = -1.0
+    let mix_machine = run_float_op(2u8, make_float(true, 33, 1, 0, 0, 0), make_float(true, 33, 2, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(false, 33, 1, 0, 0, 0)));
+
+    // Cancellation requires normalisation of the result
+    let mix_machine = run_float_op(2u8, make_float(true, 33, 1, 2, 3, 4), make_float(true, 33, 1, 2, 3, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 30, 4, 0, 0, 0)));
+
+    // x - x is zero
+    let mix_machine = run_float_op(2u8, make_float(true, 33, 1, 2, 3, 4), make_float(true, 33, 1, 2, 3, 4));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(0u32));
+}
+
+#[test]
+fn float_multiplication() {
+    // 2.0 * 0.5 = 1.0
+    let mix_machine = run_float_op(3u8, make_float(true, 33, 2, 0, 0, 0), make_float(true, 32, 32, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 33, 1, 0, 0, 0)));
+
+    // -3.0 * 3.0 = -9.0
+    let mix_machine = run_float_op(3u8, make_float(false, 33, 3, 0, 0, 0), make_float(true, 33, 3, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(false, 33, 9, 0, 0, 0)));
+}
+
+#[test]
+fn float_multiplication_exponent_overflow() {
+    let mix_machine = run_float_op(3u8, make_float(true, 63, 1, 0, 0, 0), make_float(true, 63, 1, 0, 0, 0));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(true));
+    // The exponent is given modulo the byte size
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, (63 + 63 - 32 - 1) % 64, 1, 0, 0, 0)));
+}
+
+#[test]
+fn float_multiplication_exponent_underflow() {
+    let mix_machine = run_float_op(3u8, make_float(true, 1, 1, 0, 0, 0), make_float(true, 1, 1, 0, 0, 0));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(true));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 64 + 1 + 1 - 32 - 1, 1, 0, 0, 0)));
+}
+
+#[test]
+fn float_division() {
+    // 1.0 / 3.0 = 0.333..., with the remainder rounded away
+    let mix_machine = run_float_op(4u8, make_float(true, 33, 1, 0, 0, 0), make_float(true, 33, 3, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 32, 21, 21, 21, 21)));
+
+    // 2.0 / 3.0 = 0.666..., which rounds up
+    let mix_machine = run_float_op(4u8, make_float(true, 33, 2, 0, 0, 0), make_float(false, 33, 3, 0, 0, 0));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(false, 32, 42, 42, 42, 43)));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(false));
+}
+
+#[test]
+fn float_division_by_zero() {
+    let mix_machine = run_float_op(4u8, make_float(true, 33, 1, 0, 0, 0), 0u32);
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(true));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 33, 1, 0, 0, 0)));
+}
+
+#[test]
+fn float_flot() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 6u8, 5u8)), Ok(())); // FLOT
+    assert_eq!(mix_machine.poke_register(Register::RegA, 100u32 + (1u32 << 30)), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(false, 34, 1, 36, 0, 0)));
+
+    // The largest integer needs rounding
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 6u8, 5u8)), Ok(())); // FLOT
+    assert_eq!(mix_machine.poke_register(Register::RegA, (1u32 << 30) - 1), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(make_float(true, 38, 1, 0, 0, 0)));
+}
+
+#[test]
+fn float_fix() {
+    let cases = [
+        (make_float(true, 33, 3, 0, 0, 0), 3u32, false),                    // 3.0
+        (make_float(true, 33, 2, 32, 0, 0), 2u32, false),                   // 2.5 rounds to even
+        (make_float(false, 33, 3, 32, 0, 0), 4u32 + (1u32 << 30), false),   // -3.5 rounds to even
+        (make_float(true, 33, 2, 33, 0, 0), 3u32, false),                   // 2.515625
+        (make_float(true, 20, 2, 33, 0, 0), 0u32, false),                   // Tiny
+        (make_float(true, 37, 1, 2, 3, 4), (1u32 << 24) + (2u32 << 18) + (3u32 << 12) + (4u32 << 6), false),
+        (make_float(true, 38, 1, 2, 3, 4), (2u32 << 24) + (3u32 << 18) + (4u32 << 12), true), // Too large
+    ];
+    for &(a, result, overflow) in cases.iter() {
+        let mut mix_machine = MixMachine::new();
+        assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 7u8, 5u8)), Ok(())); // FIX
+        assert_eq!(mix_machine.poke_register(Register::RegA, a), Ok(()));
+        assert_eq!(mix_machine.step(), Ok(()));
+        assert_eq!(mix_machine.peek_register(Register::RegA), Ok(result));
+        assert_eq!(mix_machine.peek_overflow_toggle(), Ok(overflow));
+    }
+}
+
+// Compare u with v, with epsilon stored in location 0
+fn run_float_comparison(u: u32, v: u32, epsilon: u32) -> ComparisonState {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 0u8, 39u8)), Ok(())); // JMP 100
+    assert_eq!(mix_machine.poke_memory(100u16, Operation::make_instruction(true, 12u16, 0u8, 5u8, 8u8)), Ok(())); // LDA 12
+    assert_eq!(mix_machine.poke_memory(101u16, Operation::make_instruction(true, 0u16, 0u8, 5u8, 24u8)), Ok(())); // STA 0
+    assert_eq!(mix_machine.poke_memory(102u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 8u8)), Ok(())); // LDA 10
+    assert_eq!(mix_machine.poke_memory(103u16, Operation::make_instruction(true, 11u16, 0u8, 6u8, 56u8)), Ok(())); // FCMP 11
+    assert_eq!(mix_machine.poke_memory(104u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(())); // HLT
+    assert_eq!(mix_machine.poke_memory(10u16, u), Ok(()));
+    assert_eq!(mix_machine.poke_memory(11u16, v), Ok(()));
+    assert_eq!(mix_machine.poke_memory(12u16, epsilon), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    mix_machine.peek_comparison_indicator().unwrap()
+}
+
+#[test]
+fn float_comparison() {
+    let one = make_float(true, 33, 1, 0, 0, 0);
+    let three = make_float(true, 33, 3, 0, 0, 0);
+    let one_and_a_bit = make_float(true, 33, 1, 0, 0, 1);     // 1 + 64^-3
+
+    assert_eq!(run_float_comparison(one, three, 0u32), ComparisonState::Less);
+    assert_eq!(run_float_comparison(three, one, 0u32), ComparisonState::Greater);
+    assert_eq!(run_float_comparison(one, one, 0u32), ComparisonState::Equal);
+    assert_eq!(run_float_comparison(one, one_and_a_bit, 0u32), ComparisonState::Less);
+
+    // |u - v| = 64^-3, and the tolerance is epsilon * 64^1
+    assert_eq!(run_float_comparison(one, one_and_a_bit, 63u32), ComparisonState::Less);
+    assert_eq!(run_float_comparison(one, one_and_a_bit, 64u32), ComparisonState::Equal);
+    assert_eq!(run_float_comparison(one_and_a_bit, one, 64u32), ComparisonState::Equal);
+    assert_eq!(run_float_comparison(one_and_a_bit, one, 63u32), ComparisonState::Greater);
+
+    // Signed comparisons
+    assert_eq!(run_float_comparison(make_float(false, 33, 1, 0, 0, 0), make_float(true, 20, 1, 0, 0, 0), 0u32), ComparisonState::Less);
+    assert_eq!(run_float_comparison(make_float(false, 33, 1, 0, 0, 0), 1u32 << 30, 0u32), ComparisonState::Less);
+    assert_eq!(run_float_comparison(0u32, 1u32 << 30, 0u32), ComparisonState::Equal);
+}
+
+#[test]
+fn float_comparison_small_difference() {
+    // The tolerance is exactly the larger operand, so a tiny v decides the comparison by its sign
+    let one = make_float(true, 33, 1, 0, 0, 0);
+    let epsilon = 1u32 << 24;   // 1/64, so the tolerance is 1.0
+    assert_eq!(run_float_comparison(one, make_float(true, 2, 1, 0, 0, 0), epsilon), ComparisonState::Equal);
+    assert_eq!(run_float_comparison(one, make_float(false, 2, 1, 0, 0, 0), epsilon), ComparisonState::Greater);
+    assert_eq!(run_float_comparison(one, 0u32, epsilon), ComparisonState::Equal);
+}
@@ -0,0 +1,54 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+
+#[test]
+fn halt_stops_machine() {
+    let mut mix_machine = MixMachine::new();
+
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(())); // HLT
+    assert!(!mix_machine.is_halted());
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert!(mix_machine.is_halted());
+
+    // Further steps report the halted state rather than fetching the next instruction
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Attempted to step a halted machine.")}));
+}
+
+#[test]
+fn run_until_halt() {
+    let mut mix_machine = MixMachine::new();
+
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 10
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 3u16, 0u8, 0u8, 48u8)), Ok(()));  // INCA 3
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));   // HLT
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 99u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 99
+
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert!(mix_machine.is_halted());
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(13u32));   // ENTA 99 must not have executed
+}
+
+#[test]
+fn run_loop_until_halt() {
+    let mut mix_machine = MixMachine::new();
+
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 5u16, 0u8, 2u8, 49u8)), Ok(())); // ENT1 5
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 2u16, 0u8, 0u8, 48u8)), Ok(())); // INCA 2
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 1u16, 0u8, 1u8, 49u8)), Ok(())); // DEC1 1
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 1u16, 0u8, 2u8, 41u8)), Ok(())); // J1P 1
+    assert_eq!(mix_machine.poke_memory(4u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));  // HLT
+
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(10u32));
+    assert_eq!(mix_machine.peek_register(Register::RegI1), Ok(0u32));
+}
+
+#[test]
+fn run_reports_errors() {
+    let mut mix_machine = MixMachine::new();
+
+    // Without a HLT, the program eventually runs off the end of memory
+    assert_eq!(mix_machine.run(), Err(MixMachineErr{message: String::from("Attempted instruction fetch from invalid memory address 4000.")}));
+    assert!(!mix_machine.is_halted());
+}
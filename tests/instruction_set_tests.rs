@@ -0,0 +1,67 @@
+extern crate mix;
+use mix::mix_operations::*;
+use mix::mix_instruction_set::*;
+use mix::mix_word::{ByteSize, Binary, Decimal};
+
+#[test]
+fn round_trip_all_op_codes_and_fields() {
+    for op_code in 0u8..64 {
+        for field in 0u8..64 {
+            let instruction = Operation::make_instruction(false, 1234, 3, field, op_code);
+            match InstructionDef::lookup(op_code, field) {
+                Some(def) => {
+                    assert!(Operation::from_u32(instruction).is_ok());
+                    assert_eq!(def.op_code, op_code);
+                    let given_field = if def.field_is_fixed { None } else { Some(field) };
+                    assert_eq!(Operation::encode::<Binary>(def.mnemonic, false, 1234, 3, given_field), Ok(instruction));
+                    assert!(Operation::disassemble::<Binary>(instruction).unwrap().starts_with(def.mnemonic));
+                },
+                None => {
+                    assert!(Operation::from_u32(instruction).is_err());
+                    assert!(Operation::disassemble::<Binary>(instruction).is_err());
+                },
+            }
+        }
+    }
+}
+
+#[test]
+fn mnemonics_are_unique() {
+    for def in INSTRUCTIONS.iter() {
+        let def_found = InstructionDef::from_mnemonic(def.mnemonic).unwrap();
+        assert_eq!((def_found.op_code, def_found.default_field), (def.op_code, def.default_field));
+        // Every fixed field instruction must be reachable by the decoder
+        if def.field_is_fixed {
+            assert_eq!(InstructionDef::lookup(def.op_code, def.default_field).unwrap().mnemonic, def.mnemonic);
+        }
+    }
+}
+
+#[test]
+fn comparison_op_codes() {
+    // Each of CMP1, ..., CMP6 has its own op code
+    assert_eq!(InstructionDef::lookup(61, 5).unwrap().mnemonic, "CMP5");
+    assert_eq!(InstructionDef::lookup(62, 5).unwrap().mnemonic, "CMP6");
+    assert_eq!(InstructionDef::lookup(63, 5).unwrap().mnemonic, "CMPX");
+}
+
+#[test]
+fn encode_instructions() {
+    assert_eq!(Operation::encode::<Binary>("LDA", true, 2000, 0, None), Ok(Operation::make_instruction(true, 2000, 0, 5, 8)));
+    assert_eq!(Operation::encode::<Binary>("STJ", true, 2000, 0, None), Ok(Operation::make_instruction(true, 2000, 0, 2, 32)));
+    assert_eq!(Operation::encode::<Binary>("FADD", true, 2000, 0, None), Ok(Operation::make_instruction(true, 2000, 0, 6, 1)));
+    assert_eq!(Operation::encode::<Decimal>("JXO", true, 2000, 1, None), Ok(2000010747u64));
+    assert_eq!(Operation::encode::<Binary>("JMP", true, 2000, 0, Some(1)), Err(()));
+    assert_eq!(Operation::encode::<Binary>("LDA", true, 4096, 0, None), Err(()));
+    assert_eq!(Operation::encode::<Binary>("LDQ", true, 2000, 0, None), Err(()));
+}
+
+#[test]
+fn disassemble_instructions() {
+    assert_eq!(Operation::disassemble::<Binary>(Operation::make_instruction(false, 2000, 3, 3, 8)), Ok(String::from("LDA -2000,3(0:3)")));
+    assert_eq!(Operation::disassemble::<Binary>(Operation::make_instruction(true, 2000, 0, 5, 8)), Ok(String::from("LDA 2000")));
+    assert_eq!(Operation::disassemble::<Binary>(Operation::make_instruction(true, 1000, 0, 3, 7)), Ok(String::from("MOVE 1000(3)")));
+    assert_eq!(Operation::disassemble::<Binary>(Operation::make_instruction(true, 0, 0, 2, 5)), Ok(String::from("HLT 0")));
+    assert_eq!(Operation::disassemble::<Decimal>(Decimal::make_instruction(true, 100, 0, 16, 36)), Ok(String::from("IN 100(16)")));
+    assert_eq!(Operation::disassemble::<Binary>(Operation::make_instruction(true, 0, 0, 3, 5)), Err(()));
+}
@@ -0,0 +1,151 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+
+// Instructions with negative addresses refer to control memory
+fn make_negative_instruction(address: u16, index_spec: u8, field_spec: u8, op_code: u8) -> u32 {
+    Operation::make_instruction(false, address, index_spec, field_spec, op_code)
+}
+
+#[test]
+fn int_saves_and_restores_registers() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 5u16, 0u8, 2u8, 48u8)), Ok(()));  // ENTA 5
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 7u16, 0u8, 3u8, 49u8)), Ok(()));  // ENN1 7
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 3u16, 0u8, 5u8, 40u8)), Ok(()));  // JANP 3 (sets rJ)
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(()));   // INT
+    assert_eq!(mix_machine.poke_memory(4u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));   // HLT
+    assert_eq!(mix_machine.poke_control_memory(-12i16, make_negative_instruction(100u16, 0u8, 0u8, 39u8)), Ok(()));  // JMP -100
+    assert_eq!(mix_machine.poke_control_memory(-100i16, Operation::make_instruction(true, 42u16, 0u8, 2u8, 48u8)), Ok(()));  // ENTA 42
+    assert_eq!(mix_machine.poke_control_memory(-99i16, make_negative_instruction(100u16, 0u8, 2u8, 49u8)), Ok(()));  // ENT1 -100
+    assert_eq!(mix_machine.poke_control_memory(-98i16, make_negative_instruction(9u16, 0u8, 5u8, 15u8)), Ok(()));  // LDX -9
+    assert_eq!(mix_machine.poke_control_memory(-97i16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(()));  // INT
+
+    for _ in 0..4 { assert_eq!(mix_machine.step(), Ok(())); }
+    assert!(mix_machine.is_in_control_state());
+    assert_eq!(mix_machine.peek_control_memory(-9i16), Ok(5u32));
+    assert_eq!(mix_machine.peek_control_memory(-8i16), Ok(7u32 + (1u32 << 30)));
+    assert_eq!(mix_machine.peek_control_memory(-2i16), Ok(0u32));
+    // Next instruction is 4, comparison indicator is LESS, rJ is 3
+    assert_eq!(mix_machine.peek_control_memory(-1i16), Ok((4u32 << 18) + 3u32));
+
+    for _ in 0..4 { assert_eq!(mix_machine.step(), Ok(())); }
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(42u32));
+    assert_eq!(mix_machine.peek_register(Register::RegI1), Ok(100u32 + (1u32 << 30)));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(5u32));
+
+    // INT in control state returns to the interrupted program
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert!(!mix_machine.is_in_control_state());
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(5u32));
+    assert_eq!(mix_machine.peek_register(Register::RegI1), Ok(7u32 + (1u32 << 30)));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(0u32));
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(3u32));
+}
+
+#[test]
+fn int_return_uses_modified_state() {
+    // The control program can change the saved state before returning
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(()));   // INT
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));   // HLT
+    assert_eq!(mix_machine.poke_memory(10u16, Operation::make_instruction(true, 3u16, 0u8, 2u8, 55u8)), Ok(())); // ENTX 3
+    assert_eq!(mix_machine.poke_memory(11u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));  // HLT
+    assert_eq!(mix_machine.poke_control_memory(-12i16, make_negative_instruction(100u16, 0u8, 0u8, 39u8)), Ok(()));  // JMP -100
+    assert_eq!(mix_machine.poke_control_memory(-100i16, Operation::make_instruction(true, 10u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 10
+    assert_eq!(mix_machine.poke_control_memory(-99i16, make_negative_instruction(1u16, 0u8, 10u8, 24u8)), Ok(()));  // STA -1(1:2)
+    assert_eq!(mix_machine.poke_control_memory(-98i16, make_negative_instruction(1u16, 0u8, 27u8, 24u8)), Ok(())); // STA -1(3:3)
+    assert_eq!(mix_machine.poke_control_memory(-97i16, Operation::make_instruction(true, 0u16, 0u8, 3u8, 48u8)), Ok(())); // ENNA 0
+    assert_eq!(mix_machine.poke_control_memory(-96i16, make_negative_instruction(9u16, 0u8, 5u8, 24u8)), Ok(()));  // STA -9
+    assert_eq!(mix_machine.poke_control_memory(-95i16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(())); // INT
+
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert!(!mix_machine.is_in_control_state());
+    // rA was -0 on return, and the program resumed at location 10 with OV set and CI GREATER
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(1u32 << 30));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(3u32));
+    assert_eq!(mix_machine.peek_overflow_toggle(), Ok(true));
+    assert_eq!(mix_machine.peek_comparison_indicator(), Ok(ComparisonState::Greater));
+}
+
+#[test]
+fn control_memory_inaccessible_in_normal_state() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, make_negative_instruction(5u16, 0u8, 5u8, 8u8)), Ok(()));  // LDA -5
+    assert!(mix_machine.step().is_err());
+
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, make_negative_instruction(5u16, 0u8, 0u8, 39u8)), Ok(()));  // JMP -5
+    assert!(mix_machine.step().is_err());
+}
+
+#[test]
+fn control_memory_bounds() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_control_memory(-3999i16, 1u32), Ok(()));
+    assert_eq!(mix_machine.peek_control_memory(-3999i16), Ok(1u32));
+    assert!(mix_machine.poke_control_memory(-4000i16, 1u32).is_err());
+    assert!(mix_machine.poke_control_memory(0i16, 1u32).is_err());
+    assert!(mix_machine.peek_control_memory(1i16).is_err());
+}
+
+#[test]
+fn interval_timer() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 1u16, 0u8, 0u8, 48u8)), Ok(())); // INCA 1
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 39u8)), Ok(())); // JMP 0
+    assert_eq!(mix_machine.poke_control_memory(-10i16, 3u32), Ok(()));
+    assert_eq!(mix_machine.poke_control_memory(-11i16, make_negative_instruction(100u16, 0u8, 0u8, 39u8)), Ok(()));  // JMP -100
+    assert_eq!(mix_machine.poke_control_memory(-100i16, Operation::make_instruction(true, 1u16, 0u8, 2u8, 55u8)), Ok(())); // ENTX 1
+    assert_eq!(mix_machine.poke_control_memory(-99i16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));  // HLT
+
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_control_memory(-10i16), Ok(2u32));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert!(mix_machine.is_in_control_state());
+    assert_eq!(mix_machine.peek_control_memory(-10i16), Ok(0u32));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(2u32));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(1u32));
+    assert_eq!(mix_machine.peek_control_memory(-1i16), Ok((1u32 << 18) + 2u32));    // rJ was set by JMP 0
+}
+
+#[test]
+fn interrupts_deferred_in_control_state() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(()));   // INT
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));   // HLT
+    assert_eq!(mix_machine.poke_control_memory(-12i16, make_negative_instruction(100u16, 0u8, 0u8, 39u8)), Ok(()));  // JMP -100
+    assert_eq!(mix_machine.poke_control_memory(-100i16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(())); // INT
+    assert_eq!(mix_machine.poke_control_memory(-200i16, Operation::make_instruction(true, 7u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 7
+    assert_eq!(mix_machine.poke_control_memory(-199i16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));  // HLT
+
+    assert_eq!(mix_machine.step(), Ok(()));  // INT
+    assert_eq!(mix_machine.request_interrupt(-200i16), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));  // JMP -100
+    assert!(mix_machine.is_in_control_state());
+    assert_eq!(mix_machine.step(), Ok(()));  // INT, back to normal state
+    assert!(!mix_machine.is_in_control_state());
+    assert_eq!(mix_machine.step(), Ok(()));  // The pending interrupt is taken before HLT, and ENTA 7 executes
+    assert!(mix_machine.is_in_control_state());
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(7u32));
+    assert_eq!(mix_machine.peek_control_memory(-1i16), Ok(1u32 << 18));
+    assert!(mix_machine.request_interrupt(5i16).is_err());
+}
+
+#[test]
+fn interval_timer_counts_time() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 3u8)), Ok(())); // MUL 10
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 3u8)), Ok(())); // MUL 10
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));  // HLT
+    assert_eq!(mix_machine.poke_control_memory(-10i16, 15u32), Ok(()));
+    assert_eq!(mix_machine.poke_control_memory(-11i16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(())); // HLT
+
+    assert_eq!(mix_machine.step(), Ok(()));  // MUL takes 10u
+    assert_eq!(mix_machine.peek_control_memory(-10i16), Ok(5u32));
+    assert_eq!(mix_machine.step(), Ok(()));  // The timer runs out part way through the second MUL
+    assert_eq!(mix_machine.peek_control_memory(-10i16), Ok(0u32));
+    assert_eq!(mix_machine.step(), Ok(()));  // The interrupt is taken, and HLT in location -11 executes
+    assert!(mix_machine.is_in_control_state());
+    assert!(mix_machine.is_halted());
+}
@@ -0,0 +1,126 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_char::*;
+use mix::mix_operations::*;
+use mix::mix_mock_device::*;
+use mix::mix_word::*;
+
+// A card reader with the given latency and two cards
+fn reader(latency: u32) -> MockDevice<Binary> {
+    let reader = MockDevice::<Binary>::new(16);
+    reader.set_latency(latency);
+    assert_eq!(reader.push_line("FIRST"), Ok(()));
+    assert_eq!(reader.push_line("SECOND"), Ok(()));
+    reader
+}
+
+// A machine with conflict checks, and a card reader with a latency of 10 on unit 16
+fn checked_machine() -> MixMachine {
+    with_reader(MixMachine::builder().io_conflict_checks(true).build().unwrap())
+}
+
+fn with_reader(mut mix_machine: MixMachine) -> MixMachine {
+    assert_eq!(mix_machine.attach_device(16, Box::new(reader(10))), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    mix_machine
+}
+
+#[test]
+fn load_from_busy_input_block() {
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 105u16, 0u8, 5u8, 8u8)), Ok(()));  // LDA 105
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("I/O conflict: instruction at 1 accesses location 105 in the block of busy unit 16.")}));
+}
+
+#[test]
+fn no_conflict_once_ready() {
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 1u16, 0u8, 16u8, 34u8)), Ok(()));   // JBUS 1(16)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 115u16, 0u8, 5u8, 8u8)), Ok(()));  // LDA 115
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 116u16, 0u8, 5u8, 24u8)), Ok(())); // STA 116
+    for _ in 0..13 {
+        assert_eq!(mix_machine.step(), Ok(()));
+    }
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(2u32));
+}
+
+#[test]
+fn store_into_busy_output_block() {
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 37u8)), Ok(())); // OUT 100(16)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 99u16, 0u8, 5u8, 24u8)), Ok(()));   // STA 99
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 24u8)), Ok(()));  // STA 100
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("I/O conflict: instruction at 2 accesses location 100 in the block of busy unit 16.")}));
+}
+
+#[test]
+fn fetch_from_busy_block() {
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 1u16, 0u8, 16u8, 36u8)), Ok(()));   // IN 1(16)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("I/O conflict: instruction at 1 accesses location 1 in the block of busy unit 16.")}));
+}
+
+#[test]
+fn double_buffering() {
+    // A second IN on the same unit waits for the first, after which the first block is free
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 200u16, 0u8, 16u8, 36u8)), Ok(())); // IN 200(16)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 8u8)), Ok(()));   // LDA 100
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 200u16, 0u8, 5u8, 8u8)), Ok(()));   // LDA 200
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("I/O conflict: instruction at 3 accesses location 200 in the block of busy unit 16.")}));
+}
+
+#[test]
+fn checks_off_by_default() {
+    let mut mix_machine = with_reader(MixMachine::new());
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 105u16, 0u8, 5u8, 8u8)), Ok(()));  // LDA 105
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+}
+
+#[test]
+fn device_reports_busy() {
+    let mut mix_machine = MixMachine::builder().io_conflict_checks(true).build().unwrap();
+    let printer = MockDevice::<Binary>::new(24);
+    assert_eq!(mix_machine.attach_device(18, Box::new(printer.clone())), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 18u8, 37u8)), Ok(())); // OUT 100(18)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 24u8)), Ok(()));  // STA 100
+    assert_eq!(mix_machine.step(), Ok(()));
+    printer.set_busy(true);
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("I/O conflict: instruction at 1 accesses location 100 in the block of busy unit 18.")}));
+}
+
+#[test]
+fn blocks_are_checked_after_the_wait() {
+    // The second IN on unit 17 waits until time 30, by which time unit 16 has finished
+    // with the block it reads into
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.attach_device(17, Box::new(reader(30))), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 300u16, 0u8, 17u8, 36u8)), Ok(())); // IN 300(17)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 17u8, 36u8)), Ok(())); // IN 100(17)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(100u16), text_to_word::<Binary>("SECON"));
+}
+
+#[test]
+fn detached_unit_frees_its_block() {
+    // A device attached in place of the reader does not inherit its block or busy time
+    let mut mix_machine = checked_machine();
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 105u16, 0u8, 5u8, 8u8)), Ok(()));  // LDA 105
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert!(mix_machine.detach_device(16).unwrap().is_some());
+    let punch = MockDevice::<Binary>::new(16);
+    punch.set_busy(true);
+    assert_eq!(mix_machine.attach_device(16, Box::new(punch)), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+}
@@ -0,0 +1,226 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_device::*;
+use mix::mix_word::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// A device with blocks of three words. Input gives 1, 2, 3, then 4, 5, 6 and so on.
+// Output and control operations are recorded in a log shared with the test.
+struct CountingDevice {
+    next: u32,
+    log: Rc<RefCell<Vec<String>>>,
+}
+
+impl Device<Binary> for CountingDevice {
+    fn block_size(&self) -> usize {
+        3
+    }
+
+    fn read(&mut self, block: &mut [u32], _rx: u32) -> Result<(), MixMachineErr> {
+        for word in block.iter_mut() {
+            self.next += 1;
+            *word = self.next;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, block: &[u32], rx: u32) -> Result<(), MixMachineErr> {
+        self.log.borrow_mut().push(format!("write {:?} with rX {}", block, rx));
+        Ok(())
+    }
+
+    fn control(&mut self, m: i16, _rx: u32) -> Result<(), MixMachineErr> {
+        self.log.borrow_mut().push(format!("control {}", m));
+        Ok(())
+    }
+}
+
+// A device supporting only the default operations
+struct InputOnlyDevice;
+
+impl Device<Binary> for InputOnlyDevice {
+    fn block_size(&self) -> usize {
+        1
+    }
+}
+
+fn machine_with_device(unit: u8) -> (MixMachine, Rc<RefCell<Vec<String>>>) {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(unit, Box::new(CountingDevice {next: 0, log: log.clone()})), Ok(()));
+    (mix_machine, log)
+}
+
+#[test]
+fn input_block() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 1u8, 16u8, 36u8)), Ok(())); // IN 100,1(16)
+    assert_eq!(mix_machine.poke_register(Register::RegI1, 3u32), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    for i in 0..6 {
+        assert_eq!(mix_machine.peek_memory(100u16 + i), Ok(i as u32 + 1));
+    }
+    assert_eq!(mix_machine.peek_memory(106u16), Ok(0u32));
+}
+
+#[test]
+fn output_and_control() {
+    let (mut mix_machine, log) = machine_with_device(18);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 200u16, 0u8, 18u8, 37u8)), Ok(())); // OUT 200(18)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(false, 1u16, 0u8, 18u8, 35u8)), Ok(())); // IOC -1(18)
+    assert_eq!(mix_machine.poke_memory(200u16, 7u32), Ok(()));
+    assert_eq!(mix_machine.poke_memory(202u16, 9u32), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, 42u32), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(*log.borrow(), vec![String::from("write [7, 0, 9] with rX 42"), String::from("control -1")]);
+}
+
+#[test]
+fn jump_ready_and_busy() {
+    let (mut mix_machine, _) = machine_with_device(0);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 0u8, 34u8)), Ok(())); // JBUS 10(0)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 20u16, 0u8, 0u8, 38u8)), Ok(())); // JRED 20(0)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(0u32));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(2u32));
+}
+
+#[test]
+fn missing_unit() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 17u8, 37u8)), Ok(())); // OUT 100(17)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 40u8, 38u8)), Ok(())); // JRED 100(40)
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction OUT 100(17) at location 0: no device attached to unit 17.")}));
+    assert_eq!(mix_machine.set_program_counter(1), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction JRED 100(40) at location 1: no device attached to unit 40.")}));
+    assert!(mix_machine.attach_device(21, Box::new(InputOnlyDevice)).is_err());
+}
+
+#[test]
+fn unsupported_operation() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(16, Box::new(InputOnlyDevice)), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 37u8)), Ok(())); // OUT 100(16)
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 16: Device does not support output.")}));
+}
+
+#[test]
+fn block_outside_memory() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 3998u16, 0u8, 16u8, 36u8)), Ok(())); // IN 3998(16)
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Block of 3 words at 3998 does not fit in memory.")}));
+    assert_eq!(mix_machine.peek_memory(3998u16), Ok(0u32));
+}
+
+#[test]
+fn io_instruction_times() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.last_step_time(), 1);
+}
+
+// A device that stays busy for a given number of JBUS or JRED tests after each input,
+// and reads a sensor value shared with the test
+struct SensorDevice {
+    reading: Rc<RefCell<u32>>,
+    busy_polls: RefCell<u32>,
+}
+
+impl Device<Binary> for SensorDevice {
+    fn block_size(&self) -> usize {
+        1
+    }
+
+    fn read(&mut self, block: &mut [u32], _rx: u32) -> Result<(), MixMachineErr> {
+        block[0] = *self.reading.borrow();
+        *self.busy_polls.borrow_mut() = 2;
+        Ok(())
+    }
+
+    fn is_busy(&self) -> bool {
+        let mut polls = self.busy_polls.borrow_mut();
+        if *polls > 0 { *polls -= 1; true } else { false }
+    }
+}
+
+#[test]
+fn busy_device() {
+    let reading = Rc::new(RefCell::new(55u32));
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(5, Box::new(SensorDevice {reading: reading.clone(), busy_polls: RefCell::new(0)})), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 36u8)), Ok(())); // IN 100(5)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 1u16, 0u8, 5u8, 34u8)), Ok(()));   // JBUS 1(5)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 4u16, 0u8, 5u8, 38u8)), Ok(()));   // JRED 4(5)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(100u16), Ok(55u32));
+    for _ in 0..3 {
+        assert_eq!(mix_machine.step(), Ok(()));     // JBUS jumps to itself twice, then falls through
+    }
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(2u32));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(3u32));
+}
+
+#[test]
+fn detach_device() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert!(mix_machine.has_device(16));
+    assert!(!mix_machine.has_device(17));
+    assert!(mix_machine.detach_device(16).unwrap().is_some());
+    assert!(!mix_machine.has_device(16));
+    assert!(mix_machine.detach_device(16).unwrap().is_none());
+    assert!(mix_machine.detach_device(21).is_err());
+}
+
+#[test]
+fn jbus_waits_for_latency() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert_eq!(mix_machine.set_device_latency(16, 10), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 1u16, 0u8, 16u8, 34u8)), Ok(()));   // JBUS 1(16)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));     // HLT
+    assert_eq!(mix_machine.is_unit_busy(16), Ok(false));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.is_unit_busy(16), Ok(true));
+    assert_eq!(mix_machine.run(), Ok(()));
+    // JBUS jumps from time 1 to time 9, and falls through at time 10 once the unit is
+    // ready. HLT then takes 10.
+    assert_eq!(mix_machine.elapsed_time(), 21);
+    assert_eq!(mix_machine.is_unit_busy(16), Ok(false));
+}
+
+#[test]
+fn io_on_busy_unit_waits() {
+    let (mut mix_machine, log) = machine_with_device(18);
+    assert_eq!(mix_machine.set_device_latency(18, 10), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 18u8, 37u8)), Ok(())); // OUT 100(18)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 5u16, 0u8, 18u8, 35u8)), Ok(()));   // IOC 5(18)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 9u16, 0u8, 18u8, 38u8)), Ok(()));   // JRED 9(18)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.last_step_time(), 1);
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.last_step_time(), 10);  // Waits from time 1 to time 10
+    assert_eq!(log.borrow().len(), 2);
+    assert_eq!(mix_machine.step(), Ok(()));         // Busy until time 20, so no jump
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(0u32));
+}
+
+#[test]
+fn busy_time_survives_clock_reset() {
+    let (mut mix_machine, _) = machine_with_device(16);
+    assert_eq!(mix_machine.set_device_latency(16, 10), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.step(), Ok(()));
+    mix_machine.reset_elapsed_time();
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.last_step_time(), 10);
+    assert!(mix_machine.set_device_latency(17, 10).is_err());
+}
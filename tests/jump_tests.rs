@@ -367,3 +367,46 @@ fn jump_i1_non_zero() {
     assert_eq!(mix_machine.step(), Ok(())); // ENTA 5
     assert_eq!(mix_machine.peek_register(Register::RegA), Ok(5u32));
 }
+
+#[test]
+fn jump_a_even() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_register(Register::RegA, 10u32 + (1u32 << 30)), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 2u16, 0u8, 6u8, 40u8)), Ok(())); // JAE 2
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 10u16, 0u8, 2u8, 55u8)), Ok(())); // ENTX 10
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 7u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 7
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 5u16, 0u8, 6u8, 40u8)), Ok(())); // JAE 5
+    assert_eq!(mix_machine.poke_memory(4u16, Operation::make_instruction(true, 20u16, 0u8, 2u8, 55u8)), Ok(())); // ENTX 20
+
+    assert_eq!(mix_machine.step(), Ok(())); // JAE 2 - should jump since A is even
+    assert_eq!(mix_machine.peek_register(Register::RegJ), Ok(1u32));
+    assert_eq!(mix_machine.step(), Ok(())); // ENTA 7
+    assert_eq!(mix_machine.step(), Ok(())); // JAE 5 - should not jump since A is odd
+    assert_eq!(mix_machine.step(), Ok(())); // ENTX 20
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(20u32));
+}
+
+#[test]
+fn jump_x_odd() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 2u16, 0u8, 7u8, 47u8)), Ok(())); // JXO 2
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 3u16, 0u8, 2u8, 55u8)), Ok(())); // ENTX 3
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 4u16, 0u8, 7u8, 47u8)), Ok(())); // JXO 4
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 5u16, 0u8, 2u8, 48u8)), Ok(())); // ENTA 5
+    assert_eq!(mix_machine.poke_memory(4u16, Operation::make_instruction(true, 9u16, 0u8, 2u8, 49u8)), Ok(())); // ENT1 9
+
+    assert_eq!(mix_machine.step(), Ok(())); // JXO 2 - should not jump since X is zero
+    assert_eq!(mix_machine.step(), Ok(())); // ENTX 3
+    assert_eq!(mix_machine.step(), Ok(())); // JXO 4 - should jump since X is odd
+    assert_eq!(mix_machine.step(), Ok(())); // ENT1 9
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(0u32));
+    assert_eq!(mix_machine.peek_register(Register::RegI1), Ok(9u32));
+}
+
+#[test]
+fn jump_parity_index_register() {
+    // There are no even or odd jumps for the index registers
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 2u16, 0u8, 6u8, 41u8)), Ok(()));
+    assert!(mix_machine.step().is_err());
+}
@@ -5,6 +5,7 @@ use mix::mix_machine::*;
 use mix::mix_operations::*;
 
 #[test]
+#[allow(redundant_semicolons)]
 fn test_load_op(){
     let mut mix_machine = MixMachine::new();
     assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 10u16, 0u8, 5u8, 8u8)).and_then(|_| {; // LDA
@@ -16,6 +17,7 @@ fn test_load_op(){
 
 // Function to generate a test for given registers.
 // Allows more complete test coverage.
+#[allow(clippy::cast_abs_to_unsigned, clippy::option_map_unit_fn, clippy::precedence)]
 fn test_load_with_offset_and_field_spec(reg_to_load: Register, reg_for_offset: Option<Register>,
                                         base_address: i16, offset_address: i16, value_to_load: u32,
                                         negative_load: bool, field_spec: u8) {
@@ -120,6 +122,7 @@ fn test_load_op_with_negative_offset_address() {
 }
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn test_for_all_registers() {
     let value_to_load = 1234u32;
     let reg_set_for_index = vec![None, Some(Register::RegI1), Some(Register::RegI2), Some(Register::RegI3),
@@ -146,12 +149,14 @@ fn test_for_all_registers() {
 }
 
  #[test]
+#[allow(clippy::erasing_op, clippy::identity_op)]
 fn test_load_with_truncated_field() {
     test_load_with_offset_and_field_spec(Register::RegX, None, 10i16, 20i16, 1234u32 + (1u32 << 30), false, 8*0 + 3);
     test_load_with_offset_and_field_spec(Register::RegX, None, 10i16, 20i16, 1234u32 + (1u32 << 30), false, 8*3 + 5);
 }
 
 #[test]
+#[allow(clippy::useless_vec)]
 fn test_for_all_registers_and_field_specs() {
     let value_to_load = 1234u32;
     let reg_set_for_index = vec![None, Some(Register::RegI1), Some(Register::RegI2), Some(Register::RegI3),
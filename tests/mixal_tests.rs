@@ -0,0 +1,353 @@
+extern crate mix;
+use mix::mix_char::*;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_word::*;
+use mix::mixal::*;
+
+const SUM: &str = "\
+* Add up the numbers at DATA
+N        EQU  3
+         ORIG 100
+DATA     CON  10
+         CON  20
+         CON  -5
+RESULT   CON  0
+TEXT     ALF SUM:        the text is five characters
+START    ENT1 N
+         ENTA 0
+LOOP     ADD  DATA-1,1
+         DEC1 1
+         J1P  LOOP
+         STA  RESULT
+         HLT
+         END  START
+";
+#[test]
+fn assemble_words_and_start() {
+    let program = assemble::<Binary>(SUM).unwrap();
+    assert_eq!(program.start, 105);
+    assert_eq!(program.words[2], (102u16, Binary::to_word(Binary::from_signed(-5))));
+    assert_eq!(program.words[4], (104u16, text_to_word::<Binary>("SUM: ").unwrap()));
+    assert_eq!(program.words[5], (105u16, Operation::make_instruction(true, 3u16, 0u8, 2u8, 49u8)));   // ENT1 3
+    assert_eq!(program.words[7], (107u16, Operation::make_instruction(true, 99u16, 1u8, 5u8, 1u8)));   // ADD 99,1
+    assert_eq!(program.words[9], (109u16, Operation::make_instruction(true, 107u16, 0u8, 2u8, 41u8))); // J1P 107
+    assert_eq!(program.words.len(), 12);
+}
+
+#[test]
+fn load_and_run() {
+    let program = assemble::<Binary>(SUM).unwrap();
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(program.load(&mut mix_machine), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(103u16), Ok(25u32));
+}
+
+#[test]
+fn decimal_machine() {
+    let program = assemble::<Decimal>(SUM).unwrap();
+    let mut mix_machine = DecimalMixMachine::new();
+    assert_eq!(program.load(&mut mix_machine), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(103u16), Ok(25u64));
+}
+
+#[test]
+fn operand_syntax() {
+    let program = assemble::<Binary>("\
+* Each part of the operand may be left out
+ LDA  -2000,3(0:3)
+ LDX  1000(5)
+ MOVE 1000(3)
+ LD2N *+2
+ NOP
+ IOC  0(18)
+ END  0
+").unwrap();
+    let words: Vec<u32> = program.words.iter().map(|&(_, word)| word).collect();
+    assert_eq!(words, vec![Operation::make_instruction(false, 2000u16, 3u8, 3u8, 8u8),
+                           Operation::make_instruction(true, 1000u16, 0u8, 5u8, 15u8),
+                           Operation::make_instruction(true, 1000u16, 0u8, 3u8, 7u8),
+                           Operation::make_instruction(true, 5u16, 0u8, 5u8, 18u8),
+                           Operation::make_instruction(true, 0u16, 0u8, 0u8, 0u8),
+                           Operation::make_instruction(true, 0u16, 0u8, 18u8, 35u8)]);
+    assert_eq!(Operation::disassemble::<Binary>(words[0]), Ok(String::from("LDA -2000,3(0:3)")));
+}
+
+#[test]
+fn listing() {
+    let program = assemble::<Binary>("\
+X    EQU  5
+     ORIG 10
+     CON  X
+     END  10
+").unwrap();
+    assert_eq!(program.listing.len(), 4);
+    assert_eq!(program.listing[0], ListingLine{line: 1, location: None, word: None, source: String::from("X    EQU  5")});
+    assert_eq!(program.listing[2], ListingLine{line: 3, location: Some(10), word: Some(5u32), source: String::from("     CON  X")});
+}
+
+#[test]
+fn errors_give_the_line() {
+    assert_eq!(assemble::<Binary>(" NOP\n FOO 1\n END 0\n").err(),
+               Some(MixalErr{line: 2, message: String::from("Unknown operation FOO.")}));
+    assert_eq!(assemble::<Binary>("A NOP\nA NOP\n END 0\n").err(),
+               Some(MixalErr{line: 2, message: String::from("Symbol A is already defined.")}));
+    assert_eq!(assemble::<Binary>(" LDA 0(1:6)\n END 0\n").err(),
+               Some(MixalErr{line: 1, message: String::from("Invalid field 1:6.")}));
+    assert!(assemble::<Binary>(" IN 1000(20)\n END 0\n").is_ok());
+    assert_eq!(assemble::<Binary>(" IN 1000(21)\n END 0\n").err(),
+               Some(MixalErr{line: 1, message: String::from("Invalid unit 21.")}));
+    assert_eq!(assemble::<Binary>(" ORIG 4095\n NOP\n NOP\n END 0\n").err(),
+               Some(MixalErr{line: 3, message: String::from("Location 4096 is outside memory.")}));
+    assert_eq!(assemble::<Binary>(" NOP\n HLT\n").err(),
+               Some(MixalErr{line: 2, message: String::from("Missing END.")}));
+}
+
+// Find the largest of the numbers at X+1 to X+N, as in TAOCP 1.3.2, Program M
+const MAXIMUM: &str = "\
+* The maximum is left in rA and its index in rI2
+N        EQU  5
+         ORIG 100
+START    ENT1 N
+         JMP  MAX
+         HLT
+MAX      STJ  EXIT
+INIT     ENT3 0,1
+         JMP  CHANGEM
+LOOP     CMPA X,3
+         JGE  *+3
+CHANGEM  ENT2 0,3
+         LDA  X,3
+         DEC3 1
+         J3P  LOOP
+EXIT     JMP  *
+X        EQU  1000
+         END  START
+";
+
+#[test]
+fn forward_references() {
+    let program = assemble::<Binary>(MAXIMUM).unwrap();
+    assert_eq!(program.words[1], (101u16, Operation::make_instruction(true, 103u16, 0u8, 0u8, 39u8)));  // JMP MAX
+    assert_eq!(program.words[3], (103u16, Operation::make_instruction(true, 112u16, 0u8, 2u8, 32u8)));  // STJ EXIT
+    assert_eq!(program.listing[4].word, Some(Operation::make_instruction(true, 103u16, 0u8, 0u8, 39u8)));
+}
+
+#[test]
+fn local_symbols() {
+    let program = assemble::<Binary>("\
+* Count down rI1 twice, using local labels for both loops
+         ORIG 10
+START    ENT1 3
+2H       DEC1 1
+         J1P  2B
+         ENT1 2
+         JMP  2F
+         NOP
+2H       DEC1 1
+         J1P  2B
+         HLT
+         END  START
+").unwrap();
+    assert_eq!(program.words[2], (12u16, Operation::make_instruction(true, 11u16, 0u8, 2u8, 41u8)));  // J1P 11
+    assert_eq!(program.words[4], (14u16, Operation::make_instruction(true, 16u16, 0u8, 0u8, 39u8)));  // JMP 16
+    assert_eq!(program.words[7], (17u16, Operation::make_instruction(true, 16u16, 0u8, 2u8, 41u8)));  // J1P 16
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(program.load(&mut mix_machine), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegI1), Ok(0u32));
+}
+
+#[test]
+fn labels_are_defined_after_the_operand() {
+    let program = assemble::<Binary>("\
+* 2B on the line of a 2H refers to the 2H before it
+2H       NOP
+2H       JMP  2B
+LOOP     JMP  LOOP
+         END  0
+").unwrap();
+    assert_eq!(program.words[1], (1u16, Operation::make_instruction(true, 0u16, 0u8, 0u8, 39u8)));   // JMP 0
+    assert_eq!(program.words[2], (2u16, Operation::make_instruction(true, 2u16, 0u8, 0u8, 39u8)));   // JMP 2
+}
+
+#[test]
+fn illegal_future_references() {
+    let future = |reference: &str| format!("Future reference {} is only allowed as the address of an instruction.", reference);
+    assert_eq!(assemble::<Binary>("A EQU B\nB EQU 1\n END 0\n").err(), Some(MixalErr{line: 1, message: future("B")}));
+    assert_eq!(assemble::<Binary>(" CON B\nB CON 1\n END 0\n").err(), Some(MixalErr{line: 1, message: future("B")}));
+    assert_eq!(assemble::<Binary>(" LDA B+1\nB CON 1\n END 0\n").err(), Some(MixalErr{line: 1, message: future("B")}));
+    assert_eq!(assemble::<Binary>(" ORIG 2F\n2H NOP\n END 0\n").err(), Some(MixalErr{line: 1, message: future("2F")}));
+    assert_eq!(assemble::<Binary>(" LDA 0,B\nB EQU 1\n END 0\n").err(), Some(MixalErr{line: 1, message: future("B")}));
+    assert_eq!(assemble::<Binary>(" JMP 2F\n END 0\n").err(), Some(MixalErr{line: 1, message: String::from("Undefined symbol 2F.")}));
+    assert_eq!(assemble::<Binary>(" JMP 2B\n END 0\n").err(), Some(MixalErr{line: 1, message: String::from("Undefined symbol 2B.")}));
+    assert_eq!(assemble::<Binary>("2B NOP\n END 0\n").err(), Some(MixalErr{line: 1, message: String::from("Invalid label 2B.")}));
+    assert_eq!(assemble::<Binary>("2H NOP\n JMP 2H\n END 0\n").err(),
+               Some(MixalErr{line: 2, message: String::from("Local symbol 2H can only be used as a label.")}));
+    assert_eq!(assemble::<Binary>(" JMP B\nB EQU 5000\n END 0\n").err(), Some(MixalErr{line: 1, message: String::from("Address 5000 is too large.")}));
+}
+
+#[test]
+fn literals_are_pooled_at_end() {
+    let program = assemble::<Binary>("\
+* Add three constants, two of them the same
+         ORIG 20
+START    LDA  =1000=
+         ADD  =1000=,1(1:5)
+         SUB  =-7=
+         HLT
+         END  START
+").unwrap();
+    assert_eq!(program.words[0], (20u16, Operation::make_instruction(true, 24u16, 0u8, 5u8, 8u8)));  // LDA 24
+    assert_eq!(program.words[1], (21u16, Operation::make_instruction(true, 24u16, 1u8, 13u8, 1u8))); // ADD 24,1(1:5)
+    assert_eq!(program.words[2], (22u16, Operation::make_instruction(true, 25u16, 0u8, 5u8, 2u8)));  // SUB 25
+    assert_eq!(program.words[4], (24u16, 1000u32));
+    assert_eq!(program.words[5], (25u16, Binary::to_word(Binary::from_signed(-7))));
+    assert_eq!(program.words.len(), 6);
+    assert_eq!(program.listing[6].source, "         END  START");
+    assert_eq!(program.listing[7], ListingLine{line: 7, location: Some(24), word: Some(1000u32), source: String::from("=1000=")});
+    assert_eq!(program.listing[8], ListingLine{line: 7, location: Some(25), word: Some(Binary::to_word(Binary::from_signed(-7))),
+                                               source: String::from("=-7=")});
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(program.load(&mut mix_machine), Ok(()));
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(2007u32));
+}
+
+#[test]
+fn undefined_symbols_follow_the_literals() {
+    let program = assemble::<Binary>("\
+* COUNT and TEMP are never defined, so each gets a word of +0
+         ORIG 10
+START    LDA  =5=
+         STA  TEMP
+         LDX  COUNT
+         STX  TEMP
+         HLT
+         END  START
+").unwrap();
+    assert_eq!(program.words[1], (11u16, Operation::make_instruction(true, 16u16, 0u8, 5u8, 24u8)));  // STA 16
+    assert_eq!(program.words[2], (12u16, Operation::make_instruction(true, 17u16, 0u8, 5u8, 15u8)));  // LDX 17
+    assert_eq!(program.words[3], (13u16, Operation::make_instruction(true, 16u16, 0u8, 5u8, 31u8)));  // STX 16
+    assert_eq!(&program.words[5..], &[(15u16, 5u32), (16u16, 0u32), (17u16, 0u32)]);
+    assert_eq!(program.listing[9], ListingLine{line: 8, location: Some(16), word: Some(0u32), source: String::from("TEMP")});
+    assert_eq!(program.listing[10], ListingLine{line: 8, location: Some(17), word: Some(0u32), source: String::from("COUNT")});
+}
+
+#[test]
+fn end_label_follows_the_pooled_words() {
+    let program = assemble::<Binary>("\
+* FIN comes after the literal and TEMP, as if their lines came just before END
+         ORIG 10
+START    LDA  =7=
+         STA  TEMP
+         JMP  FIN
+FIN      END  START
+").unwrap();
+    assert_eq!(program.words[2], (12u16, Operation::make_instruction(true, 15u16, 0u8, 0u8, 39u8)));  // JMP 15
+    assert_eq!(&program.words[3..], &[(13u16, 7u32), (14u16, 0u32)]);
+    let program = assemble::<Binary>(" ORIG 10\n LDA =7=\n ENTX FIN\nFIN END 10\n").unwrap();
+    assert_eq!(program.words[1], (11u16, Operation::make_instruction(true, 13u16, 0u8, 2u8, 55u8)));  // ENTX 13
+}
+
+#[test]
+fn literal_errors() {
+    assert_eq!(assemble::<Binary>(" LDA =1000\n END 0\n").err(),
+               Some(MixalErr{line: 1, message: String::from("Unterminated literal =1000.")}));
+    assert_eq!(assemble::<Binary>(" LDA =B=\nB CON 0\n END 0\n").err(),
+               Some(MixalErr{line: 1, message: String::from("Future reference B is only allowed as the address of an instruction.")}));
+    assert_eq!(assemble::<Binary>(" ORIG 4095\n LDA =1=\n END 0\n").err(),
+               Some(MixalErr{line: 3, message: String::from("Location 4096 is outside memory.")}));
+}
+
+// The word assembled for CON with the given operand, at location 10
+fn con<B: ByteSize>(operand: &str) -> Result<B::Word, MixalErr> {
+    assemble::<B>(&format!(" ORIG 10\n CON {}\n END 0\n", operand)).map(|program| program.words[0].1)
+}
+
+fn signed(value: i64) -> u32 {
+    Binary::to_word(Binary::from_signed(value))
+}
+
+#[test]
+fn expressions_are_evaluated_left_to_right() {
+    assert_eq!(con::<Binary>("1+2*3"), Ok(9u32));
+    assert_eq!(con::<Binary>("-1+5"), Ok(4u32));
+    assert_eq!(con::<Binary>("+7/2"), Ok(3u32));
+    assert_eq!(con::<Binary>("-7/2"), Ok(signed(-3)));
+    assert_eq!(con::<Binary>("1:3"), Ok(11u32));
+    assert_eq!(con::<Binary>("2-3:4"), Ok(signed(-4)));
+    assert_eq!(con::<Binary>("***"), Ok(100u32));
+    assert_eq!(con::<Binary>("1**"), Ok(10u32));
+    assert_eq!(con::<Binary>("*-3*2"), Ok(14u32));
+    assert_eq!(con::<Binary>("1//3"), Ok(357913941u32));
+    assert_eq!(con::<Decimal>("1//3"), Ok(3333333333u64));
+    assert_eq!(con::<Binary>("-1//4"), Ok(signed(-268435456)));
+}
+
+#[test]
+fn expressions_keep_five_bytes_on_overflow() {
+    assert_eq!(con::<Binary>("1073741823+2"), Ok(1u32));
+    assert_eq!(con::<Binary>("-1073741823-1"), Ok(Binary::make_word(false, [0, 0, 0, 0, 0])));
+    assert_eq!(con::<Binary>("32768*32769"), Ok(32768u32));
+    assert_eq!(con::<Decimal>("9999999999+2"), Ok(1u64));
+    assert_eq!(con::<Binary>("5//3").err(), Some(MixalErr{line: 2, message: String::from("Overflow in 5//3.")}));
+    assert_eq!(con::<Binary>("1/0").err(), Some(MixalErr{line: 2, message: String::from("Division by zero in 1/0.")}));
+    assert_eq!(con::<Binary>("1073741824").err(), Some(MixalErr{line: 2, message: String::from("Number 1073741824 does not fit in a word.")}));
+    assert_eq!(con::<Binary>("1+").err(), Some(MixalErr{line: 2, message: String::from("Invalid expression 1+.")}));
+}
+
+#[test]
+fn minus_zero_is_kept() {
+    let minus_zero = Binary::make_word(false, [0, 0, 0, 0, 0]);
+    assert_eq!(con::<Binary>("-0"), Ok(minus_zero));
+    assert_eq!(con::<Binary>("-0+0"), Ok(minus_zero));
+    assert_eq!(con::<Binary>("-0*5"), Ok(minus_zero));
+    assert_eq!(con::<Binary>("-0(0:2)"), Ok(minus_zero));
+    assert_eq!(con::<Binary>("3-3"), Ok(0u32));
+    let program = assemble::<Binary>("\
+* -0 as an address, as a symbol and as a literal, which is not the same as =0=
+Z        EQU  -0
+         ENTA -0
+         ENTX Z
+         LDA  =-0=
+         LDX  =0=
+         END  0
+").unwrap();
+    assert_eq!(program.words[0].1, Operation::make_instruction(false, 0u16, 0u8, 2u8, 48u8));  // ENTA -0
+    assert_eq!(program.words[1].1, Operation::make_instruction(false, 0u16, 0u8, 2u8, 55u8));  // ENTX -0
+    assert_eq!(program.words[2].1, Operation::make_instruction(true, 4u16, 0u8, 5u8, 8u8));    // LDA 4
+    assert_eq!(program.words[3].1, Operation::make_instruction(true, 5u16, 0u8, 5u8, 15u8));   // LDX 5
+    assert_eq!(&program.words[4..], &[(4u16, minus_zero), (5u16, 0u32)]);
+}
+
+#[test]
+fn w_values() {
+    assert_eq!(con::<Binary>("1(1:2),5(4:5)"), Ok(Binary::make_word(true, [0, 1, 0, 0, 5])));
+    assert_eq!(con::<Binary>("1,-1000(0:2)"), Ok(Binary::make_word(false, [15, 40, 0, 0, 1])));
+    assert_eq!(con::<Decimal>("1,-1000(0:2)"), Ok(Decimal::make_word(false, [10, 0, 0, 0, 1])));
+    assert_eq!(con::<Binary>("-1(0:0)"), Ok(Binary::make_word(false, [0, 0, 0, 0, 0])));
+    assert_eq!(con::<Binary>("65(4:4)"), Ok(Binary::make_word(true, [0, 0, 0, 1, 0])));
+    assert_eq!(con::<Binary>("1(1:6)").err(),
+               Some(MixalErr{line: 2, message: String::from("Field specification 14 has R=6. Must have R<=5.")}));
+    assert_eq!(con::<Binary>("1(2:1)").err(),
+               Some(MixalErr{line: 2, message: String::from("Field specification 17 has L=2, R=1. Must have L<=R.")}));
+    assert_eq!(con::<Binary>("1(2").err(), Some(MixalErr{line: 2, message: String::from("Invalid W-value 1(2.")}));
+}
+
+#[test]
+fn w_values_in_pseudo_operations_and_literals() {
+    let program = assemble::<Binary>("\
+* The W-value 1(4:4) is 64, and 2(1:2),3 is 3
+X        EQU  1(4:4)
+         ORIG X+2(4:5)
+START    LDA  =1(1:2),5(4:5)=
+         HLT
+         END  2(1:2),START
+").unwrap();
+    assert_eq!(program.start, 66);
+    assert_eq!(program.words[0], (66u16, Operation::make_instruction(true, 68u16, 0u8, 5u8, 8u8)));
+    assert_eq!(program.words[2], (68u16, Binary::make_word(true, [0, 1, 0, 0, 5])));
+}
@@ -0,0 +1,88 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_mock_device::*;
+use mix::mix_word::*;
+
+#[test]
+fn scripted_input_and_captured_output() {
+    let device = MockDevice::<Binary>::new(14);
+    assert_eq!(device.push_line("HELLO"), Ok(()));
+    assert_eq!(device.push_input(&[1u32, 2u32]), Ok(()));
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(19, Box::new(device.clone())), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 19u8, 36u8)), Ok(())); // IN 100(19)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 19u8, 37u8)), Ok(())); // OUT 100(19)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 200u16, 0u8, 19u8, 36u8)), Ok(())); // IN 200(19)
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 100u16, 0u8, 19u8, 36u8)), Ok(())); // IN 100(19)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(201u16), Ok(2u32));
+    assert_eq!(device.input_remaining(), 0);
+    assert_eq!(device.output_lines(), vec![String::from("HELLO")]);
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 19: No more input for the mock device.")}));
+}
+
+#[test]
+fn calls_are_recorded_in_order() {
+    let device = MockDevice::<Binary>::new(2);
+    assert_eq!(device.push_input(&[7u32]), Ok(()));
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(8, Box::new(device.clone())), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, 3u32), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 0u16, 0u8, 8u8, 35u8)), Ok(()));    // IOC 0(8)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 8u8, 36u8)), Ok(()));  // IN 100(8)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 8u8, 37u8)), Ok(()));  // OUT 100(8)
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(false, 1u16, 0u8, 8u8, 35u8)), Ok(()));   // IOC -1(8)
+    for _ in 0..4 {
+        assert_eq!(mix_machine.step(), Ok(()));
+    }
+    assert_eq!(device.calls(), vec![DeviceCall::Control{m: 0, rx: 3u32},
+                                    DeviceCall::In{rx: 3u32},
+                                    DeviceCall::Out{block: vec![7u32, 0u32], rx: 3u32},
+                                    DeviceCall::Control{m: -1, rx: 3u32}]);
+    assert_eq!(device.output(), vec![vec![7u32, 0u32]]);
+}
+
+#[test]
+fn busy_and_latency() {
+    let device = MockDevice::<Decimal>::new(1);
+    device.set_latency(5);
+    device.set_busy(true);
+    let mut mix_machine = DecimalMixMachine::new();
+    assert_eq!(mix_machine.attach_device(16, Box::new(device.clone())), Ok(()));
+    assert_eq!(mix_machine.is_unit_busy(16), Ok(true));
+    device.set_busy(false);
+    assert_eq!(mix_machine.is_unit_busy(16), Ok(false));
+    assert_eq!(mix_machine.poke_memory(0u16, Decimal::make_instruction(true, 100u16, 0u8, 16u8, 37u8)), Ok(())); // OUT 100(16)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.is_unit_busy(16), Ok(true));
+}
+
+#[test]
+fn overlong_input() {
+    let device = MockDevice::<Binary>::new(1);
+    assert!(device.push_input(&[1u32, 2u32]).is_err());
+    assert!(device.push_line("TOO LONG").is_err());
+}
+
+#[test]
+fn io_waits_while_device_is_busy() {
+    let device = MockDevice::<Binary>::new(1);
+    assert_eq!(device.push_input(&[7u32]), Ok(()));
+    device.set_busy(true);
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(16, Box::new(device.clone())), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 16u8, 36u8)), Ok(())); // IN 100(16)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));     // HLT
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert!(device.calls().is_empty());
+    assert_eq!(mix_machine.peek_memory(100u16), Ok(0u32));
+    device.set_busy(false);
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(device.calls(), vec![DeviceCall::In{rx: 0u32}]);
+    assert_eq!(mix_machine.peek_memory(100u16), Ok(7u32));
+    assert_eq!(mix_machine.elapsed_time(), 13);     // Three tries of IN, then HLT
+}
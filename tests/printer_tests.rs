@@ -0,0 +1,75 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_printer::*;
+use mix::mix_char::*;
+use mix::mix_word::*;
+use std::cell::RefCell;
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+
+// Output shared between the printer and the test
+#[derive(Clone)]
+struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn machine_with_printer(printer: fn(Box<dyn Write>) -> LinePrinter) -> (MixMachine, SharedOutput) {
+    let output = SharedOutput(Rc::new(RefCell::new(Vec::new())));
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(18, Box::new(printer(Box::new(output.clone())))), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 18u8, 37u8)), Ok(())); // OUT 100(18)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 18u8, 35u8)), Ok(()));   // IOC 0(18)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 18u8, 37u8)), Ok(())); // OUT 100(18)
+    (mix_machine, output)
+}
+
+fn printed(output: &SharedOutput) -> String {
+    String::from_utf8(output.0.borrow().clone()).unwrap()
+}
+
+#[test]
+fn print_lines_and_pages() {
+    let (mut mix_machine, output) = machine_with_printer(LinePrinter::new);
+    assert_eq!(mix_machine.poke_memory(100u16, text_to_word::<Binary>("Σ +1=").unwrap()), Ok(()));
+    assert_eq!(mix_machine.poke_memory(123u16, text_to_word::<Binary>("    .").unwrap()), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    let line = format!("Σ +1={}.\n", " ".repeat(114));
+    assert_eq!(printed(&output), format!("{}\u{c}{}", line, line));
+}
+
+#[test]
+fn trailing_blanks_and_unprintable_codes() {
+    let (mut mix_machine, output) = machine_with_printer(LinePrinter::new);
+    assert_eq!(mix_machine.poke_memory(100u16, Binary::make_word(true, [1, 56, 63, 0, 0])), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(printed(&output), "A??\n");
+}
+
+#[test]
+fn page_separator() {
+    let (mut mix_machine, output) = machine_with_printer(|output| LinePrinter::new(output).page_separator("----\n"));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(printed(&output), "\n----\n\n");
+}
+
+#[test]
+fn invalid_control() {
+    let (mut mix_machine, _) = machine_with_printer(LinePrinter::new);
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 1u16, 0u8, 18u8, 35u8)), Ok(()));   // IOC 1(18)
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 18: Invalid control operation 1 for a line printer.")}));
+}
@@ -4,6 +4,7 @@ use mix::mix_machine::*;
 use mix::mix_operations::*;
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_a() {
     let mut mix_machine = MixMachine::new();
 
@@ -17,6 +18,7 @@ fn shift_left_a() {
 
 #[test]
 // Check that the sign bit is unaffected
+#[allow(unused_parens)]
 fn shift_left_a_negative() {
     let mut mix_machine = MixMachine::new();
 
@@ -29,6 +31,7 @@ fn shift_left_a_negative() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_a() {
     let mut mix_machine = MixMachine::new();
 
@@ -41,6 +44,7 @@ fn shift_right_a() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_a_negative() {
     let mut mix_machine = MixMachine::new();
 
@@ -53,6 +57,7 @@ fn shift_right_a_negative() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax() {
     let mut mix_machine = MixMachine::new();
 
@@ -67,6 +72,7 @@ fn shift_left_ax() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax_large() {
     let mut mix_machine = MixMachine::new();
 
@@ -81,6 +87,7 @@ fn shift_left_ax_large() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax_negative() {
     let mut mix_machine = MixMachine::new();
 
@@ -95,6 +102,7 @@ fn shift_left_ax_negative() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax_circulating() {
     let mut mix_machine = MixMachine::new();
 
@@ -109,6 +117,7 @@ fn shift_left_ax_circulating() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_ax() {
     let mut mix_machine = MixMachine::new();
 
@@ -123,6 +132,7 @@ fn shift_right_ax() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_ax_large() {
     let mut mix_machine = MixMachine::new();
 
@@ -137,6 +147,7 @@ fn shift_right_ax_large() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_ax_circulating() {
     let mut mix_machine = MixMachine::new();
 
@@ -151,6 +162,7 @@ fn shift_right_ax_circulating() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax_circulating_large() {
     let mut mix_machine = MixMachine::new();
 
@@ -165,6 +177,7 @@ fn shift_left_ax_circulating_large() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_ax_circulating_large() {
     let mut mix_machine = MixMachine::new();
 
@@ -179,6 +192,7 @@ fn shift_right_ax_circulating_large() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_ax_circulating_five() {
     let mut mix_machine = MixMachine::new();
 
@@ -193,6 +207,7 @@ fn shift_right_ax_circulating_five() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_right_ax_circulating_ten() {
     let mut mix_machine = MixMachine::new();
 
@@ -207,6 +222,7 @@ fn shift_right_ax_circulating_ten() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax_circulating_five() {
     let mut mix_machine = MixMachine::new();
 
@@ -221,6 +237,7 @@ fn shift_left_ax_circulating_five() {
 }
 
 #[test]
+#[allow(unused_parens)]
 fn shift_left_ax_circulating_ten() {
     let mut mix_machine = MixMachine::new();
 
@@ -233,3 +250,43 @@ fn shift_left_ax_circulating_ten() {
     assert_eq!(mix_machine.peek_register(Register::RegA), Ok((1u32 << 24) + (2u32 << 18) + (3u32 << 12) + (4u32 << 6) + 5u32 ));
     assert_eq!(mix_machine.peek_register(Register::RegX), Ok((6u32 << 24) + (7u32 << 18) + (8u32 << 12) + (9u32 << 6) + 10u32));
 }
+
+#[test]
+fn shift_left_ax_binary() {
+    let mut mix_machine = MixMachine::new();
+
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 3u16, 0u8, 6u8, 6u8)), Ok(())); // SLB 3
+    assert_eq!(mix_machine.poke_register(Register::RegA, (1u32 << 30) + (1u32 << 26) + 5u32), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, (7u32 << 27) + 1u32), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(())); // Bits leaving the top of rA are lost, bits from rX move into rA
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok((1u32 << 30) + (1u32 << 29) + (5u32 << 3) + 7u32));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(8u32));
+}
+
+#[test]
+fn shift_right_ax_binary() {
+    let mut mix_machine = MixMachine::new();
+
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 1u16, 1u8, 7u8, 6u8)), Ok(())); // SRB 1, 1
+    assert_eq!(mix_machine.poke_register(Register::RegI1, 3u32), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegA, (1u32 << 29) + 21u32), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, (1u32 << 30) + 16u32 + 15u32), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(())); // Should shift right (1 + rI1) = 4 bits
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok((1u32 << 25) + 1u32));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok((1u32 << 30) + (5u32 << 26) + 1u32));
+}
+
+#[test]
+fn shift_ax_binary_everything() {
+    let mut mix_machine = MixMachine::new();
+
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 60u16, 0u8, 6u8, 6u8)), Ok(())); // SLB 60
+    assert_eq!(mix_machine.poke_register(Register::RegA, (1u32 << 31) - 1u32), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegX, (1u32 << 30) - 1u32), Ok(()));
+
+    assert_eq!(mix_machine.step(), Ok(())); // Only the signs remain
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(1u32 << 30));
+    assert_eq!(mix_machine.peek_register(Register::RegX), Ok(0u32));
+}
@@ -7,6 +7,7 @@ use mix::mix_operations::*;
 // specification!
 
 #[test]
+#[allow(unused_must_use)]
 fn test_store_op() {
     let mut mix_machine = MixMachine::new();
     // Store STX into memory
@@ -22,6 +23,7 @@ fn test_store_op() {
 }
 
 #[test]
+#[allow(clippy::identity_op, unused_must_use)]
 fn test_store_op_field_32() {
     let mut mix_machine = MixMachine::new();
     // Store STX(2:3) into memory
@@ -38,6 +40,7 @@ fn test_store_op_field_32() {
 }
 
 #[test]
+#[allow(clippy::erasing_op, clippy::identity_op, unused_must_use)]
 fn test_store_op_field_16() {
     let mut mix_machine = MixMachine::new();
     // Store STI2(0:4) into memory
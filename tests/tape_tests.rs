@@ -0,0 +1,103 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_device::*;
+use mix::mix_tape::*;
+use mix::mix_word::*;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+// A path for a tape file, removing any left by an earlier run
+fn tape_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("mix_tape_{}_{}", name, process::id()));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn write_block(tape: &mut TapeUnit, first: u32) {
+    let block: Vec<u32> = (0..TAPE_BLOCK_SIZE as u32).map(|i| first + i).collect();
+    assert_eq!(Device::<Binary>::write(tape, &block, 0u32), Ok(()));
+}
+
+#[test]
+fn write_rewind_and_read() {
+    let path = tape_path("rewind");
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(3, Box::new(TapeUnit::open(&path).unwrap())), Ok(()));
+    for i in 0..100u16 {
+        assert_eq!(mix_machine.poke_memory(1000u16 + i, i as u32), Ok(()));
+    }
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 1000u16, 0u8, 3u8, 37u8)), Ok(())); // OUT 1000(3)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 3u8, 35u8)), Ok(()));    // IOC 0(3)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 2000u16, 0u8, 3u8, 36u8)), Ok(())); // IN 2000(3)
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 2000u16, 0u8, 3u8, 36u8)), Ok(())); // IN 2000(3)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    for i in 0..100u16 {
+        assert_eq!(mix_machine.peek_memory(2000u16 + i), Ok(i as u32));
+    }
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 3: Attempt to read past the end of the tape.")}));
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn skip_blocks() {
+    let path = tape_path("skip");
+    let mut tape = TapeUnit::open(&path).unwrap();
+    for i in 0..4 {
+        write_block(&mut tape, 1000 * i);
+    }
+    assert_eq!(tape.length(), Ok(4));
+    assert_eq!(Device::<Binary>::control(&mut tape, -3, 0u32), Ok(()));
+    assert_eq!(tape.position(), 1);
+    assert_eq!(Device::<Binary>::control(&mut tape, -5, 0u32), Ok(()));
+    assert_eq!(tape.position(), 0);
+    assert_eq!(Device::<Binary>::control(&mut tape, 2, 0u32), Ok(()));
+    let mut block = vec![0u32; TAPE_BLOCK_SIZE];
+    assert_eq!(Device::<Binary>::read(&mut tape, &mut block, 0u32), Ok(()));
+    assert_eq!(block[1], 2001u32);
+    assert_eq!(tape.position(), 3);
+    assert!(Device::<Binary>::control(&mut tape, 2, 0u32).is_err());
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn writing_erases_the_rest_of_the_tape() {
+    let path = tape_path("erase");
+    let mut tape = TapeUnit::open(&path).unwrap();
+    for i in 0..3 {
+        write_block(&mut tape, 1000 * i);
+    }
+    assert_eq!(Device::<Binary>::control(&mut tape, -2, 0u32), Ok(()));
+    write_block(&mut tape, 7000);
+    assert_eq!(tape.length(), Ok(2));
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn tape_persists_between_runs() {
+    let path = tape_path("persist");
+    {
+        let mut tape = TapeUnit::open(&path).unwrap();
+        write_block(&mut tape, 0);
+        write_block(&mut tape, 500);
+    }
+    let mut tape = TapeUnit::open(&path).unwrap();
+    assert_eq!(tape.position(), 0);
+    assert_eq!(tape.length(), Ok(2));
+    assert_eq!(tape.block::<Binary>(1).unwrap()[99], 599u32);
+    assert_eq!(tape.block::<Binary>(0).unwrap()[0], 0u32);
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn decimal_tape() {
+    let path = tape_path("decimal");
+    let mut tape = TapeUnit::open(&path).unwrap();
+    let block = vec![Decimal::make_word(false, [99, 0, 0, 0, 1]); TAPE_BLOCK_SIZE];
+    assert_eq!(Device::<Decimal>::write(&mut tape, &block, 0u64), Ok(()));
+    assert_eq!(tape.block::<Decimal>(0), Ok(block));
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,89 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_terminal::*;
+use mix::mix_char::*;
+use mix::mix_word::*;
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::io::{Cursor, Write};
+use std::process;
+use std::rc::Rc;
+
+// Output shared between the typewriter and the test
+#[derive(Clone)]
+struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+impl Write for SharedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn typewriter_echo() {
+    let output = SharedOutput(Rc::new(RefCell::new(Vec::new())));
+    let typewriter = Typewriter::new(Box::new(Cursor::new("what is 2+2\n")), Box::new(output.clone()));
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(19, Box::new(typewriter)), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 19u8, 36u8)), Ok(())); // IN 100(19)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 19u8, 37u8)), Ok(())); // OUT 100(19)
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 100u16, 0u8, 19u8, 36u8)), Ok(())); // IN 100(19)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(100u16), text_to_word::<Binary>("WHAT"));
+    assert_eq!(mix_machine.peek_memory(113u16), Ok(0u32));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(String::from_utf8(output.0.borrow().clone()).unwrap(), "WHAT IS 2+2\n");
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 19: End of input on the typewriter.")}));
+}
+
+#[test]
+fn paper_tape() {
+    let path = std::env::temp_dir().join(format!("mix_paper_tape_{}", process::id()));
+    assert!(fs::write(&path, "FIRST\nSECOND\n").is_ok());
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(20, Box::new(PaperTape::open(&path).unwrap())), Ok(()));
+    let in_tape = Operation::make_instruction(true, 100u16, 0u8, 20u8, 36u8);  // IN 100(20)
+    for i in 0..3u16 {
+        assert_eq!(mix_machine.poke_memory(i, in_tape), Ok(()));
+    }
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 0u16, 0u8, 20u8, 35u8)), Ok(()));  // IOC 0(20)
+    assert_eq!(mix_machine.poke_memory(4u16, in_tape), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(100u16), text_to_word::<Binary>("SECON"));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Unit 20: Attempt to read past the end of the paper tape.")}));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_memory(100u16), text_to_word::<Binary>("FIRST"));
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn paper_tape_errors_name_the_device() {
+    let path = std::env::temp_dir().join(format!("mix_missing_directory_{}", process::id())).join("tape");
+    let message = PaperTape::open(&path).err().unwrap().message;
+    assert!(message.starts_with("Paper tape I/O error:"), "{}", message);
+}
+
+#[test]
+fn punch_paper_tape() {
+    let path = std::env::temp_dir().join(format!("mix_punched_paper_tape_{}", process::id()));
+    assert!(fs::write(&path, "FIRST\nSECOND\nTHIRD\n").is_ok());
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.attach_device(20, Box::new(PaperTape::open(&path).unwrap())), Ok(()));
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 20u8, 36u8)), Ok(())); // IN 100(20)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 200u16, 0u8, 20u8, 37u8)), Ok(())); // OUT 200(20)
+    assert_eq!(mix_machine.poke_memory(200u16, text_to_word::<Binary>("PUNCH").unwrap()), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    // The punched line replaces the rest of the tape
+    assert_eq!(fs::read_to_string(&path).unwrap(), "FIRST\nPUNCH\n");
+    let _ = fs::remove_file(&path);
+}
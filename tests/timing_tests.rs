@@ -0,0 +1,74 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+
+// Run a single instruction at location 0 and return its execution time
+fn time_instruction(instruction: u32) -> u32 {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, instruction), Ok(()));
+    assert_eq!(mix_machine.poke_memory(100u16, 1u32), Ok(()));
+    assert_eq!(mix_machine.poke_register(Register::RegA, 1u32), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.elapsed_time(), mix_machine.last_step_time() as u64);
+    mix_machine.last_step_time()
+}
+
+#[test]
+fn instruction_times() {
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 8u8)), 2);  // LDA
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 31u8)), 2); // STX
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 1u8)), 2);  // ADD
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 2u8)), 2);  // SUB
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 3u8)), 10); // MUL
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 4u8)), 12); // DIV
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 2u8, 48u8)), 1); // ENTA
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 5u8, 56u8)), 2); // CMPA
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 0u8, 39u8)), 1); // JMP
+    assert_eq!(time_instruction(Operation::make_instruction(true, 1u16, 0u8, 0u8, 6u8)), 2);    // SLA
+    assert_eq!(time_instruction(Operation::make_instruction(true, 0u16, 0u8, 0u8, 0u8)), 1);    // NOP
+    assert_eq!(time_instruction(Operation::make_instruction(true, 0u16, 0u8, 0u8, 5u8)), 10);   // NUM
+    assert_eq!(time_instruction(Operation::make_instruction(true, 0u16, 0u8, 1u8, 5u8)), 10);   // CHAR
+    assert_eq!(time_instruction(Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), 10);   // HLT
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 6u8, 1u8)), 4);  // FADD
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 6u8, 3u8)), 9);  // FMUL
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 6u8, 4u8)), 11); // FDIV
+    assert_eq!(time_instruction(Operation::make_instruction(true, 0u16, 0u8, 6u8, 5u8)), 3);    // FLOT
+}
+
+#[test]
+fn move_time() {
+    // MOVE takes 1 + 2F
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 0u8, 7u8)), 1);
+    assert_eq!(time_instruction(Operation::make_instruction(true, 100u16, 0u8, 3u8, 7u8)), 7);
+}
+
+#[test]
+fn program_time() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 3u16, 0u8, 2u8, 49u8)), Ok(())); // ENT1 3
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 1u8)), Ok(())); // ADD 100
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 1u16, 0u8, 1u8, 49u8)), Ok(())); // DEC1 1
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 1u16, 0u8, 2u8, 41u8)), Ok(())); // J1P 1
+    assert_eq!(mix_machine.poke_memory(4u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));  // HLT
+    assert_eq!(mix_machine.poke_memory(100u16, 5u32), Ok(()));
+
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(15u32));
+    // 1u for ENT1, 3 * (2u + 1u + 1u) for the loop, and 10u for HLT
+    assert_eq!(mix_machine.elapsed_time(), 1 + 3 * 4 + 10);
+    assert_eq!(mix_machine.last_step_time(), 10);
+
+    mix_machine.reset_elapsed_time();
+    assert_eq!(mix_machine.elapsed_time(), 0);
+    assert_eq!(mix_machine.last_step_time(), 0);
+}
+
+#[test]
+fn failed_step_takes_no_time() {
+    let mut mix_machine = MixMachine::new();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 8u8)), Ok(())); // LDA 100
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 7u8, 8u8)), Ok(())); // LDA 100(0:7)
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert!(mix_machine.step().is_err());
+    assert_eq!(mix_machine.elapsed_time(), 2);
+}
@@ -0,0 +1,106 @@
+extern crate mix;
+use mix::mix_machine::*;
+use mix::mix_operations::*;
+use mix::mix_trap::*;
+use mix::mix_word::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn stop_on_invalid_field() {
+    let mut mix_machine = MixMachine::new();
+    let instruction = Operation::make_instruction(true, 100u16, 0u8, 7u8, 8u8);  // LDA 100(0:7)
+    assert_eq!(mix_machine.poke_memory(0u16, instruction), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction LDA 100(0:7) at location 0: invalid field specification.")}));
+    assert_eq!(mix_machine.elapsed_time(), 0);
+}
+
+#[test]
+fn stop_on_invalid_special_field() {
+    let mut mix_machine = MixMachine::new();
+    let instruction = Operation::make_instruction(true, 0u16, 0u8, 3u8, 5u8);    // Op code 5 has nothing at F=3
+    assert_eq!(mix_machine.poke_memory(0u16, instruction), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: format!("Illegal instruction {} at location 0: invalid field specification.", instruction)}));
+}
+
+#[test]
+fn stop_on_invalid_index() {
+    let mut mix_machine = MixMachine::new();
+    let instruction = Operation::make_instruction(true, 100u16, 7u8, 5u8, 8u8);  // LDA 100,7
+    assert_eq!(mix_machine.poke_memory(0u16, instruction), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction LDA 100,7 at location 0: invalid index specification.")}));
+}
+
+#[test]
+fn stop_on_illegal_op_code() {
+    // Op codes 64 to 99 only exist on a decimal machine, and none of them are used
+    let mut mix_machine = DecimalMixMachine::new();
+    let instruction = Decimal::make_instruction(true, 100u16, 0u8, 5u8, 64u8);
+    assert_eq!(mix_machine.poke_memory(0u16, instruction), Ok(()));
+    assert_eq!(mix_machine.step(), Err(MixMachineErr{message: String::from("Illegal instruction 100000564 at location 0: illegal op code.")}));
+}
+
+#[test]
+fn skip_illegal_instructions() {
+    let mut mix_machine = MixMachine::builder().trap_policy(TrapPolicy::Skip).build().unwrap();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 7u8, 8u8)), Ok(()));   // LDA 100(0:7)
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 100u16, 0u8, 5u8, 8u8)), Ok(()));   // LDA 100
+    assert_eq!(mix_machine.poke_memory(100u16, 17u32), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(0u32));
+    assert_eq!(mix_machine.last_step_time(), 0);
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert_eq!(mix_machine.peek_register(Register::RegA), Ok(17u32));
+}
+
+#[test]
+fn trap_handler() {
+    let traps = Rc::new(RefCell::new(Vec::new()));
+    let traps_seen = traps.clone();
+    let mut mix_machine = MixMachine::new();
+    mix_machine.set_trap_policy(TrapPolicy::Handler(Box::new(move |trap: &Trap<u32>| {
+        traps_seen.borrow_mut().push(*trap);
+        if trap.kind == TrapKind::InvalidIndex { TrapAction::Stop } else { TrapAction::Skip }
+    })));
+    let first = Operation::make_instruction(true, 0u16, 0u8, 3u8, 5u8);
+    let second = Operation::make_instruction(true, 100u16, 9u8, 5u8, 8u8);
+    assert_eq!(mix_machine.poke_memory(0u16, first), Ok(()));
+    assert_eq!(mix_machine.poke_memory(1u16, second), Ok(()));
+    assert_eq!(mix_machine.step(), Ok(()));
+    assert!(mix_machine.step().is_err());
+    assert_eq!(*traps.borrow(), vec![Trap {kind: TrapKind::InvalidField, location: 0, word: first},
+                                     Trap {kind: TrapKind::InvalidIndex, location: 1, word: second}]);
+}
+
+#[test]
+fn skip_unavailable_instructions() {
+    let mut mix_machine = MixMachine::builder().floating_point(false).interrupts(false).binary_extensions(false)
+        .trap_policy(TrapPolicy::Skip).build().unwrap();
+    assert_eq!(mix_machine.poke_memory(0u16, Operation::make_instruction(true, 100u16, 0u8, 6u8, 1u8)), Ok(()));   // FADD 100
+    assert_eq!(mix_machine.poke_memory(1u16, Operation::make_instruction(true, 0u16, 0u8, 9u8, 5u8)), Ok(()));     // INT
+    assert_eq!(mix_machine.poke_memory(2u16, Operation::make_instruction(true, 1u16, 0u8, 6u8, 6u8)), Ok(()));     // SLB 1
+    assert_eq!(mix_machine.poke_memory(3u16, Operation::make_instruction(true, 0u16, 0u8, 7u8, 40u8)), Ok(()));    // JAO 0
+    assert_eq!(mix_machine.poke_memory(4u16, Operation::make_instruction(true, 100u16, 0u8, 18u8, 37u8)), Ok(()));  // OUT 100(18)
+    assert_eq!(mix_machine.poke_memory(5u16, Operation::make_instruction(true, 0u16, 0u8, 2u8, 5u8)), Ok(()));     // HLT
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(mix_machine.elapsed_time(), 10);    // Only HLT took any time
+}
+
+#[test]
+fn handler_sees_unavailable_instructions() {
+    let traps = Rc::new(RefCell::new(Vec::new()));
+    let traps_seen = traps.clone();
+    let mut mix_machine = DecimalMixMachine::new();
+    mix_machine.set_trap_policy(TrapPolicy::Handler(Box::new(move |trap: &Trap<u64>| {
+        traps_seen.borrow_mut().push(*trap);
+        TrapAction::Skip
+    })));
+    let slb = Decimal::make_instruction(true, 1, 0, 6, 6);
+    let jbus = Decimal::make_instruction(true, 0, 0, 5, 34);
+    assert_eq!(mix_machine.poke_memory(0u16, slb), Ok(()));
+    assert_eq!(mix_machine.poke_memory(1u16, jbus), Ok(()));      // JBUS 0(5), with no tape on unit 5
+    assert_eq!(mix_machine.poke_memory(2u16, Decimal::make_instruction(true, 0, 0, 2, 5)), Ok(()));  // HLT
+    assert_eq!(mix_machine.run(), Ok(()));
+    assert_eq!(*traps.borrow(), vec![Trap {kind: TrapKind::Unavailable, location: 0, word: slb},
+                                     Trap {kind: TrapKind::NoDevice(5), location: 1, word: jbus}]);
+}